@@ -0,0 +1,25 @@
+//! Prints a CSV trace of day 1's dial, one row per rotation.
+//!
+//! ```sh
+//! cargo run --example dial_trace -- input/Challenge1.txt > trace.csv
+//! ```
+
+use anyhow::{Context, Result};
+
+use aoc::challenge::{
+    AOCChallenge,
+    challenge_1::{Challenge, Dial, trace_csv},
+};
+
+fn main() -> Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "input/Challenge1.txt".to_string());
+    let input = std::fs::read_to_string(&path)
+        .with_context(|| format!("Couldn't read input file {path}"))?;
+
+    let rotations = Challenge.parse(&input)?;
+    print!("{}", trace_csv(&Dial::default().trace(&rotations)));
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::{
+    fmt::Display,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::challenge::{Answer, Part};
+
+pub fn default_answers_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("answers/{day}.toml"))
+}
+
+/// The answers file that sits next to an input, like `example.toml` for `example.txt`.
+pub fn sibling_answers_path(input: &Path) -> PathBuf {
+    input.with_extension("toml")
+}
+
+/// Known-correct answers for an input, read from a TOML file like:
+///
+/// ```toml
+/// part_1 = 1234
+/// part_2 = "ABCD"
+/// ```
+///
+/// Either part may be left out if its answer isn't known yet.
+#[derive(Debug, Default, Clone)]
+pub struct ExpectedAnswers {
+    part_1: Option<Answer>,
+    part_2: Option<Answer>,
+}
+
+impl ExpectedAnswers {
+    /// Loads the expected answers from a file, treating a missing file as having none.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("Couldn't read answers file {}", path.display()));
+            }
+        };
+
+        Self::parse(&contents)
+            .with_context(|| format!("Couldn't parse answers file {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let table = contents.parse::<toml::Table>()?;
+
+        if let Some(key) = table
+            .keys()
+            .find(|key| !matches!(key.as_str(), "part_1" | "part_2"))
+        {
+            bail!("Unexpected key `{key}`, expected `part_1` or `part_2`");
+        }
+
+        let answer = |key: &str| {
+            table
+                .get(key)
+                .map(|value| match value {
+                    toml::Value::Integer(n) => Ok(Answer::Integer(*n)),
+                    toml::Value::String(s) => {
+                        let Ok(answer) = s.parse();
+                        Ok(answer)
+                    }
+                    other => Err(anyhow!(
+                        "`{key}` should be an integer or string, not {}",
+                        other.type_str()
+                    )),
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+
+    pub fn part(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part_1.as_ref(),
+            Part::Two => self.part_2.as_ref(),
+        }
+    }
+
+    pub fn check(&self, part: Part, answer: &Answer) -> Verdict {
+        match self.part(part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There's no expected answer to check against.
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Verdict::Fail { .. })
+    }
+
+    /// The answer that was expected, if the check failed.
+    pub fn expected(&self) -> Option<&Answer> {
+        match self {
+            Verdict::Fail { expected } => Some(expected),
+            _ => None,
+        }
+    }
+
+    /// The verdict without the expected answer, for compact tables.
+    pub fn short(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "fail, expected {expected}"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use serde::{Serialize, Serializer};
+
+use crate::{
+    challenge::{ChallengeInfo, Part},
+    logging::DayScope,
+};
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "as_nanos")]
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub day: u8,
+    pub iterations: u32,
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl BenchReport {
+    /// Each timed stage with its name.
+    pub fn stages(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part 1", &self.part_1),
+            ("part 2", &self.part_2),
+        ]
+    }
+}
+
+/// Times the parse step and each part separately, `iterations` times over.
+pub fn bench(info: &ChallengeInfo, input: &str, iterations: u32) -> Result<BenchReport> {
+    if iterations == 0 {
+        bail!("Need at least one iteration to benchmark");
+    }
+
+    let _scope = DayScope::enter(info.day);
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+
+    for _ in 0..iterations {
+        let challenge = (info.constructor)();
+
+        let start = Instant::now();
+        let prepared = challenge.prepare(input)?;
+        samples[0].push(start.elapsed());
+
+        for (part, part_samples) in [Part::One, Part::Two].into_iter().zip(&mut samples[1..]) {
+            let start = Instant::now();
+            prepared.run_part(part)?;
+            part_samples.push(start.elapsed());
+        }
+    }
+
+    let [parse, part_1, part_2] = samples.map(Stats::from_samples);
+
+    Ok(BenchReport {
+        day: info.day,
+        iterations,
+        parse,
+        part_1,
+        part_2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples() {
+        let stats = Stats::from_samples([4, 1, 3, 2].map(Duration::from_millis).to_vec());
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+
+        let stats = Stats::from_samples([5, 1, 9].map(Duration::from_millis).to_vec());
+
+        assert_eq!(stats.median, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn needs_an_iteration() {
+        let info = &crate::challenge::CHALLENGES[0];
+
+        assert!(bench(info, "", 0).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+use serde::{Serialize, Serializer};
+
+/// The answer to one part of a challenge.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    String(String),
+    /// The part hasn't been solved yet.
+    Unsolved,
+}
+
+impl Answer {
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(n) => Some(*n as i128),
+            Answer::BigInteger(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::String(a), Answer::String(b)) => a == b,
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => self.as_i128().is_some_and(|a| Some(a) == other.as_i128()),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::String(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Numbers serialize as numbers and strings as strings, with an unsolved part as `null`.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            Answer::BigInteger(n) => serializer.serialize_i128(*n),
+            Answer::String(s) => serializer.serialize_str(s),
+            Answer::Unsolved => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Numbers are parsed into the narrowest integer variant that fits; anything else is kept as a
+/// string.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(n) = s.parse::<i64>() {
+            Answer::Integer(n)
+        } else if let Ok(n) = s.parse::<i128>() {
+            Answer::BigInteger(n)
+        } else {
+            Answer::String(s.to_string())
+        })
+    }
+}
+
+macro_rules! impl_from_int {
+    ( $($t:ty),+ ) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Integer(n),
+                        Err(_) => Answer::BigInteger(n as i128),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::from(n),
+            Err(_) => Answer::String(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
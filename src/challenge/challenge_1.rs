@@ -1,12 +1,18 @@
-use anyhow::anyhow;
+use std::fmt::Display;
+
+use anyhow::{anyhow, bail};
+use log::trace;
 use regex::Regex;
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{ParseError, parse_at};
 
 #[derive(Debug, Default)]
 pub struct Challenge;
 
-#[derive(PartialEq, Eq)]
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(1, "Secret Entrance");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Left,
     Right,
@@ -24,52 +30,375 @@ impl TryFrom<&str> for Direction {
     }
 }
 
+/// A dial with positions numbered from 0 to `size - 1`, which counts how often it points at 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+    ends_on_zero: u128,
+    passes_zero: u128,
+}
+
+impl Default for Dial {
+    /// The puzzle's dial: 100 positions, starting at 50.
+    fn default() -> Self {
+        Self {
+            size: 100,
+            position: 50,
+            ends_on_zero: 0,
+            passes_zero: 0,
+        }
+    }
+}
+
+impl Dial {
+    pub fn new(size: u64, start: u64) -> anyhow::Result<Self> {
+        if start >= size {
+            bail!("A dial with {size} positions can't start at {start}");
+        }
+
+        Ok(Self {
+            size,
+            position: start,
+            ends_on_zero: 0,
+            passes_zero: 0,
+        })
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// How many rotations have left the dial pointing at 0.
+    pub fn ends_on_zero(&self) -> u128 {
+        self.ends_on_zero
+    }
+
+    /// How many clicks have left the dial pointing at 0, including the last click of a rotation.
+    pub fn passes_zero(&self) -> u128 {
+        self.passes_zero
+    }
+
+    /// Turns the dial by some number of clicks, returning how many of them pointed it at 0.
+    pub fn rotate(&mut self, direction: Direction, clicks: u64) -> u64 {
+        let (size, clicks) = (self.size as u128, clicks as u128);
+
+        // Turning left is turning right on a mirrored dial, where 0 stays in the same place.
+        let start = match direction {
+            Direction::Right => self.position as u128,
+            Direction::Left => (size - self.position as u128) % size,
+        };
+        let end = (start + clicks) % size;
+        // Every multiple of `size` in `start + 1..=start + clicks` is a click onto 0.
+        let zeros = ((start + clicks) / size) as u64;
+
+        self.position = match direction {
+            Direction::Right => end,
+            Direction::Left => (size - end) % size,
+        } as u64;
+        self.passes_zero += zeros as u128;
+        if self.position == 0 {
+            self.ends_on_zero += 1;
+        }
+
+        zeros
+    }
+
+    /// Turns the dial one click at a time, returning how many clicks pointed it at 0. This is far
+    /// slower than [`Dial::rotate`], but obviously right, so it's used to check it.
+    pub fn rotate_click_by_click(&mut self, direction: Direction, clicks: u64) -> u64 {
+        let mut zeros = 0;
+
+        for _ in 0..clicks {
+            self.position = match direction {
+                Direction::Right => (self.position + 1) % self.size,
+                Direction::Left => (self.position + self.size - 1) % self.size,
+            };
+            if self.position == 0 {
+                zeros += 1;
+            }
+        }
+
+        self.passes_zero += zeros as u128;
+        if self.position == 0 {
+            self.ends_on_zero += 1;
+        }
+
+        zeros
+    }
+
+    /// Runs the rotations through both [`Dial::rotate`] and [`Dial::rotate_click_by_click`],
+    /// returning the first one where they disagree.
+    pub fn find_disagreement(&self, rotations: &[(Direction, u64)]) -> Option<Disagreement> {
+        let (mut closed_form, mut simulated) = (self.clone(), self.clone());
+
+        rotations
+            .iter()
+            .enumerate()
+            .find_map(|(index, &(direction, clicks))| {
+                let start = closed_form.position();
+                let closed_form = (
+                    closed_form.rotate(direction, clicks),
+                    closed_form.position(),
+                );
+                let simulated = (
+                    simulated.rotate_click_by_click(direction, clicks),
+                    simulated.position(),
+                );
+
+                (closed_form != simulated).then_some(Disagreement {
+                    index,
+                    rotation: (direction, clicks),
+                    start,
+                    closed_form: (closed_form.1, closed_form.0),
+                    simulated: (simulated.1, simulated.0),
+                })
+            })
+    }
+
+    /// Turns the dial through every rotation, recording each one.
+    pub fn trace(&mut self, rotations: &[(Direction, u64)]) -> Vec<TraceStep> {
+        rotations
+            .iter()
+            .map(|&(direction, clicks)| {
+                let start = self.position;
+                let zeros = self.rotate(direction, clicks);
+
+                TraceStep {
+                    direction,
+                    clicks,
+                    start,
+                    end: self.position,
+                    zeros,
+                    ends_on_zero: self.ends_on_zero,
+                    passes_zero: self.passes_zero,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A rotation where [`Dial::rotate`] and [`Dial::rotate_click_by_click`] disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    /// The rotation's index in the sequence.
+    pub index: usize,
+    pub rotation: (Direction, u64),
+    pub start: u64,
+    /// The end position and zero count from [`Dial::rotate`].
+    pub closed_form: (u64, u64),
+    /// The end position and zero count from [`Dial::rotate_click_by_click`].
+    pub simulated: (u64, u64),
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (direction, clicks) = self.rotation;
+        write!(
+            f,
+            "rotation {} ({direction:?} {clicks}) from {}: the closed form ends at {} after {} zeros, \
+             but the simulation ends at {} after {} zeros",
+            self.index,
+            self.start,
+            self.closed_form.0,
+            self.closed_form.1,
+            self.simulated.0,
+            self.simulated.1
+        )
+    }
+}
+
+/// What happened to the dial during one rotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub direction: Direction,
+    pub clicks: u64,
+    pub start: u64,
+    pub end: u64,
+    /// How many clicks of this rotation pointed the dial at 0.
+    pub zeros: u64,
+    /// [`Dial::ends_on_zero`] after this rotation.
+    pub ends_on_zero: u128,
+    /// [`Dial::passes_zero`] after this rotation.
+    pub passes_zero: u128,
+}
+
+/// Writes a trace as CSV, with a header row and one row per rotation.
+pub fn trace_csv(steps: &[TraceStep]) -> String {
+    let mut csv =
+        "rotation,direction,clicks,start,end,zeros,ends_on_zero,passes_zero\n".to_string();
+
+    for (i, step) in steps.iter().enumerate() {
+        let direction = match step.direction {
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        csv += &format!(
+            "{},{direction},{},{},{},{},{},{}\n",
+            i + 1,
+            step.clicks,
+            step.start,
+            step.end,
+            step.zeros,
+            step.ends_on_zero,
+            step.passes_zero
+        );
+    }
+
+    csv
+}
+
+impl Challenge {
+    fn turn_dial(rotations: &[(Direction, u64)]) -> Dial {
+        let mut dial = Dial::default();
+
+        for step in dial.trace(rotations) {
+            trace!("{step:?}");
+        }
+
+        dial
+    }
+}
+
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
-        let re = Regex::new("([LR])(\\d+)").unwrap();
+    type Input<'a> = Vec<(Direction, u64)>;
 
-        let (final_angle, end_zero_count, total_zero_count) = input
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+        let re = Regex::new("([LR])(\\d+)")?;
+
+        input
             .lines()
-            .map(|line| re.captures(line).unwrap().extract::<2>().1)
-            .try_fold(
-                (50, 0, 0),
-                |(mut old_angle, mut end_zero_count, mut total_zero_count),
-                 [direction, turn]|
-                 -> anyhow::Result<(i16, u32, u32)> {
-                    println!("{direction} {turn}");
-                    let direction = Direction::try_from(direction)?;
-                    let turn = u16::from_str_radix(turn, 10)?;
-
-                    let mut new_angle = match direction {
-                        Direction::Left => old_angle
-                            .checked_sub_unsigned(turn)
-                            .ok_or(anyhow!("Underflow error"))?,
-                        Direction::Right => old_angle
-                            .checked_add_unsigned(turn)
-                            .ok_or(anyhow!("Overflow error"))?,
-                    };
-
-                    total_zero_count += new_angle.div_euclid(100).abs() as u32;
-                    if new_angle < 0 && old_angle == 0 {
-                        total_zero_count -= 1;
-                    }
-                    new_angle = new_angle.rem_euclid(100);
-
-                    if new_angle == 0 {
-                        end_zero_count += 1;
-                        if direction == Direction::Left {
-                            total_zero_count += 1;
-                        }
-                    }
-
-                    println!("> {old_angle} => {new_angle} {total_zero_count}");
-                    Ok((new_angle, end_zero_count, total_zero_count))
-                },
-            )?;
-
-        Ok(AOCResult::new(
-            end_zero_count.to_string(),
-            total_zero_count.to_string(),
-        ))
+            .map(|line| -> anyhow::Result<_> {
+                let [direction, turn] = re
+                    .captures(line)
+                    .ok_or_else(|| ParseError::at(input, line, "a rotation like `L68`"))?
+                    .extract()
+                    .1;
+
+                Ok((
+                    Direction::try_from(direction)?,
+                    parse_at(input, turn, "a number of clicks")?,
+                ))
+            })
+            .collect()
+    }
+
+    fn part_1(&self, rotations: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(Self::turn_dial(rotations).ends_on_zero().into())
+    }
+
+    fn part_2(&self, rotations: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(Self::turn_dial(rotations).passes_zero().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example() {
+        check_example(Challenge, 1, "example");
+    }
+
+    #[test]
+    fn malformed_rotation() {
+        let error = Challenge.parse("L68\nQ5\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a rotation like `L68`, found `Q5`"
+        );
+    }
+
+    #[test]
+    fn small_dial() {
+        let mut dial = Dial::new(5, 0).unwrap();
+
+        assert_eq!(dial.rotate(Direction::Left, 5), 1);
+        assert_eq!(dial.rotate(Direction::Right, 12), 2);
+        assert_eq!(dial.position(), 2);
+        assert_eq!(dial.rotate(Direction::Left, 2), 1);
+        assert_eq!((dial.ends_on_zero(), dial.passes_zero()), (2, 4));
+
+        assert!(Dial::new(5, 5).is_err());
+    }
+
+    #[test]
+    fn huge_rotations() {
+        let mut dial = Dial::default();
+
+        assert_eq!(dial.rotate(Direction::Right, u64::MAX), 184467440737095516);
+        assert_eq!(dial.position(), 65);
+        assert_eq!(dial.rotate(Direction::Left, u64::MAX), 184467440737095516);
+        assert_eq!(dial.position(), 50);
+    }
+
+    /// A xorshift generator, so the random rotations are the same on every run.
+    fn random_rotations(seed: u64, count: usize, max_clicks: u64) -> Vec<(Direction, u64)> {
+        let mut state = seed;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        (0..count)
+            .map(|_| {
+                let direction = if next() % 2 == 0 {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                };
+                (direction, next() % (max_clicks + 1))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn closed_form_matches_simulation() {
+        for seed in 1..=50 {
+            for size in [1, 2, 3, 7, 100] {
+                let dial = Dial::new(size, seed % size).unwrap();
+                let rotations = random_rotations(seed, 200, 3 * size);
+
+                if let Some(disagreement) = dial.find_disagreement(&rotations) {
+                    panic!("Seed {seed}, size {size}: {disagreement}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn disagreements_are_described() {
+        let disagreement = Disagreement {
+            index: 3,
+            rotation: (Direction::Left, 150),
+            start: 0,
+            closed_form: (50, 2),
+            simulated: (50, 1),
+        };
+
+        assert_eq!(
+            disagreement.to_string(),
+            "rotation 3 (Left 150) from 0: the closed form ends at 50 after 2 zeros, but the \
+             simulation ends at 50 after 1 zeros"
+        );
+    }
+
+    #[test]
+    fn trace_of_the_example() {
+        let rotations = Challenge.parse("L68\nL30\nR48\n").unwrap();
+        let steps = Dial::default().trace(&rotations);
+
+        assert_eq!(
+            trace_csv(&steps),
+            "rotation,direction,clicks,start,end,zeros,ends_on_zero,passes_zero\n\
+             1,L,68,50,82,1,0,1\n\
+             2,L,30,82,52,0,0,1\n\
+             3,R,48,52,0,1,1,2\n"
+        );
     }
 }
@@ -5,15 +5,17 @@ use std::{
     str::FromStr,
 };
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use fxhash::FxHashSet;
 use good_lp::*;
 use itertools::Itertools;
+use log::trace;
 use nalgebra::{DMatrix, DVector, LU, SVD, zero};
 use rayon::prelude::*;
 use regex::Regex;
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::{runner, utils::ParseError};
 
 const EPS: f64 = 0.00001;
 fn is_whole(f: f64) -> bool {
@@ -23,8 +25,10 @@ fn is_whole(f: f64) -> bool {
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(10, "Factory");
+
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     light_requirements: Lights,
     buttons: Vec<Box<[usize]>>,
     joltage_requirements: Box<[u64]>,
@@ -215,7 +219,7 @@ impl FromStr for Machine {
             .ok_or(anyhow!("Couldn't parse joltage"))?
             .as_str();
 
-        println!("[{lights_str}] {buttons_str} {{{joltage_str}}}");
+        trace!("[{lights_str}] {buttons_str} {{{joltage_str}}}");
 
         let (lights, num_lights) = lights_str
             .chars()
@@ -226,9 +230,8 @@ impl FromStr for Machine {
             .map(|button_str| {
                 button_str
                     .strip_prefix("(")
-                    .unwrap()
-                    .strip_suffix(")")
-                    .unwrap()
+                    .and_then(|s| s.strip_suffix(")"))
+                    .ok_or(anyhow!("Couldn't parse button {button_str}"))?
                     .split(",")
                     .map(|num| Ok(str::parse::<usize>(num)?))
                     .collect::<Result<Box<[_]>>>()
@@ -248,18 +251,60 @@ impl FromStr for Machine {
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
-        let machines = input
+    type Input<'a> = Vec<Machine>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+        input
             .lines()
-            .map(|line| Machine::from_str(line))
-            .collect::<Result<Vec<_>>>()?;
+            .map(|line| {
+                Machine::from_str(line).with_context(|| {
+                    ParseError::at(input, line, "a machine like `[.##.] (3) (1,3) {3,5}`")
+                })
+            })
+            .collect()
+    }
 
+    fn part_1(&self, machines: &Self::Input<'_>) -> anyhow::Result<Answer> {
         let lights_presses: u64 = machines.par_iter().map(Machine::optimise_lights).sum();
-        let joltage_presses: u64 = machines.iter().map(Machine::optimise_joltages).sum();
 
-        Ok(AOCResult {
-            part_1: lights_presses.to_string(),
-            part_2: joltage_presses.to_string(),
-        })
+        Ok(lights_presses.into())
+    }
+
+    fn part_2(&self, machines: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        // Each machine is its own integer program, and there are a lot of them, so give up between
+        // machines once the run has timed out.
+        let joltage_presses = machines
+            .iter()
+            .map(|machine| {
+                if runner::cancelled() {
+                    bail!("Cancelled after timing out");
+                }
+                Ok(machine.optimise_joltages())
+            })
+            .sum::<Result<u64>>()?;
+
+        Ok(joltage_presses.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example() {
+        check_example(Challenge, 10, "example");
+    }
+
+    #[test]
+    fn malformed_machine_keeps_the_cause() {
+        let error = Challenge.parse("[.#] (0,,1) {1,2}").unwrap_err();
+
+        assert_eq!(
+            format!("{error:#}"),
+            "line 1, column 1: expected a machine like `[.##.] (3) (1,3) {3,5}`, found \
+             `[.#] (0,,1) {1,2}`: cannot parse integer from empty string"
+        );
     }
 }
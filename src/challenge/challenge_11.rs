@@ -9,7 +9,8 @@ use petgraph::{
     visit::EdgeFiltered,
 };
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::ParseError;
 
 struct Bin;
 impl<A> FromIterator<A> for Bin {
@@ -21,40 +22,83 @@ impl<A> FromIterator<A> for Bin {
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(11, "Reactor");
+
+pub struct Reactor<'a> {
+    graph: Acyclic<StableDiGraph<&'a str, ()>>,
+    node_indices: FxHashMap<&'a str, NodeIndex>,
+}
+
+impl Reactor<'_> {
+    fn node(&self, name: &str) -> anyhow::Result<NodeIndex> {
+        self.node_indices
+            .get(name)
+            .copied()
+            .ok_or(anyhow!("No device named {name}"))
+    }
+}
+
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
+    type Input<'a> = Reactor<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
         let mut graph: Acyclic<StableDiGraph<&str, ()>> = Acyclic::new();
 
-        let node_indices = input
+        let devices = input
             .lines()
-            .map(|line| line.split_once(": ").unwrap())
-            .chain(std::iter::once(("out", "")))
-            .map(|(node, _)| (node, graph.add_node(node)))
+            .map(|line| {
+                line.split_once(": ")
+                    .ok_or_else(|| ParseError::at(input, line, "a device like `aaa: you hhh`"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let node_indices = devices
+            .iter()
+            .map(|(node, _)| *node)
+            .chain(std::iter::once("out"))
+            .map(|node| (node, graph.add_node(node)))
             .collect::<FxHashMap<_, _>>();
 
-        let edges = input
-            .lines()
-            .map(|line| line.split_once(": ").unwrap())
-            .flat_map(|(input, outputs)| {
-                outputs
-                    .split_whitespace()
-                    .map(|output| (node_indices[input], node_indices[output]))
+        devices
+            .iter()
+            .flat_map(|(node, outputs)| {
+                outputs.split_whitespace().map(move |output| (node, output))
             })
-            .try_for_each(|(a, b)| graph.try_add_edge(a, b, ()).map(|_| ()))
-            .map_err(|_| anyhow!("Tried to create an edge cycle"))?;
+            .try_for_each(|(node, output)| -> anyhow::Result<()> {
+                let output = node_indices.get(output).ok_or_else(|| {
+                    ParseError::at(input, output, "a device that has its own line, or `out`")
+                })?;
+
+                graph
+                    .try_add_edge(node_indices[node], *output, ())
+                    .map_err(|_| anyhow!("Tried to create an edge cycle"))?;
+                Ok(())
+            })?;
+
+        Ok(Reactor {
+            graph,
+            node_indices,
+        })
+    }
+
+    fn part_1(&self, reactor: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let you = reactor.node("you")?;
+        let out = reactor.node("out")?;
 
-        // let you = node_indices["you"];
-        let out = node_indices["out"];
+        let path_count =
+            all_simple_paths::<Bin, _, FxBuildHasher>(&reactor.graph, you, out, 0, None).count();
 
-        // let path_count =
-        //     all_simple_paths::<Vec<_>, _, FxBuildHasher>(&graph, you, out, 0, None).count();
-        let path_count = 0;
+        Ok(path_count.into())
+    }
 
-        let svr = node_indices["svr"];
-        let fft = node_indices["fft"];
-        let dac = node_indices["dac"];
+    fn part_2(&self, reactor: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let graph = &reactor.graph;
+        let out = reactor.node("out")?;
+        let svr = reactor.node("svr")?;
+        let fft = reactor.node("fft")?;
+        let dac = reactor.node("dac")?;
 
-        let sorted = toposort(&graph, None).unwrap();
+        let sorted = toposort(graph, None).unwrap();
 
         let [(first_pos, &first), (last_pos, &last)] = sorted
             .iter()
@@ -86,9 +130,22 @@ impl AOCChallenge for Challenge {
 
         let path_count_via = paths_start_to_first * paths_first_to_last * paths_last_to_end;
 
-        Ok(AOCResult {
-            part_1: path_count.to_string(),
-            part_2: path_count_via.to_string(),
-        })
+        Ok(path_count_via.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example_1() {
+        check_example(Challenge, 11, "example_1");
+    }
+
+    #[test]
+    fn example_2() {
+        check_example(Challenge, 11, "example_2");
     }
 }
@@ -1,33 +1,175 @@
-use anyhow::anyhow;
-use itertools::Itertools;
-use regex::Regex;
+use std::ops::RangeInclusive;
 
-use super::{AOCChallenge, AOCResult};
+use log::trace;
 
-const fn decimal_rsh(num: u64, digits: u32) -> u64 {
-    let pow10 = 10u64.pow(digits);
-    num / pow10
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{ParseError, RangeSet, parse_at};
+
+#[derive(Debug, Default)]
+pub struct Challenge;
+
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(2, "Gift Shop");
+
+/// The distinct prime factors of `n`.
+fn prime_factors(mut n: u32) -> Vec<u32> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while n > 1 {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    factors
 }
 
-const fn decimal_lsh(num: u64, digits: u32) -> u64 {
-    let pow10 = 10u64.pow(digits);
-    num * pow10
+/// The sum of the `digits`-digit numbers in `lo..=hi` that are a `block`-digit block repeated,
+/// like 123123 for a 3-digit block. `lo` and `hi` must both have `digits` digits.
+fn sum_of_repeats(lo: u128, hi: u128, digits: u32, block: u32) -> u128 {
+    // Repeating a block is multiplying it by 1001, 10101 and so on.
+    let multiplier = (10u128.pow(digits) - 1) / (10u128.pow(block) - 1);
+    let first = 10u128.pow(block - 1).max(lo.div_ceil(multiplier));
+    let last = (10u128.pow(block) - 1).min(hi / multiplier);
+
+    if first > last {
+        return 0;
+    }
+    multiplier * (first + last) * (last - first + 1) / 2
 }
 
-fn decimal_substr(num: u64, start: u32, end: u32) -> u64 {
-    let num_digits = num.ilog10() + 1;
-    let lower = decimal_rsh(num, num_digits - end);
-    // println!("{lower}");
-    let upper = decimal_lsh(decimal_rsh(lower, end - start), end - start);
-    // println!("{upper}");
+/// The sum of the IDs in a range made of a block of digits repeated twice or, with
+/// `any_repetitions`, any number of times.
+///
+/// Rather than checking every ID, this builds the repeated numbers for each length of ID directly,
+/// so it takes time in the number of digits rather than the width of the range.
+fn sum_of_invalid_ids(range: &RangeInclusive<u64>, any_repetitions: bool) -> u128 {
+    (1..=u64::MAX.ilog10() + 1)
+        .map(|digits| {
+            let lo = (*range.start() as u128).max(10u128.pow(digits - 1));
+            let hi = (*range.end() as u128).min(10u128.pow(digits) - 1);
+            if lo > hi {
+                return 0;
+            }
+
+            if !any_repetitions {
+                return match digits % 2 {
+                    0 => sum_of_repeats(lo, hi, digits, digits / 2),
+                    _ => 0,
+                };
+            }
+
+            // An ID made of repeated blocks is also made of the blocks `digits / p` long, for
+            // some prime `p` dividing `digits`. IDs like 111111 repeat blocks of several of these
+            // lengths, so inclusion-exclusion over the primes counts each of them once.
+            let primes = prime_factors(digits);
+            let total = (1..1u32 << primes.len())
+                .map(|subset| {
+                    let divisor = (0..primes.len())
+                        .filter(|i| subset & (1 << i) != 0)
+                        .map(|i| primes[i])
+                        .product::<u32>();
+                    let sum = sum_of_repeats(lo, hi, digits, digits / divisor) as i128;
 
-    lower - upper
+                    if subset.count_ones() % 2 == 1 {
+                        sum
+                    } else {
+                        -sum
+                    }
+                })
+                .sum::<i128>();
+
+            total as u128
+        })
+        .sum()
 }
 
-#[derive(Debug, Default)]
-pub struct Challenge;
+impl AOCChallenge for Challenge {
+    type Input<'a> = RangeSet;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+        let list = input.trim();
+        let list = list.strip_suffix(',').unwrap_or(list);
+        if list.is_empty() {
+            return Ok(RangeSet::default());
+        }
+
+        let mut ranges = list
+            .split(',')
+            .map(|entry| -> anyhow::Result<_> {
+                let entry = entry.trim();
+                let (start, end) = entry
+                    .split_once('-')
+                    .ok_or_else(|| ParseError::at(input, entry, "a range like `11-22`"))?;
+                let start = parse_at(input, start, "an ID that fits in 64 bits")?;
+                let end = parse_at(input, end, "an ID that fits in 64 bits")?;
+
+                if start > end {
+                    return Err(ParseError::at(
+                        input,
+                        entry,
+                        "a range that doesn't end before it starts",
+                    )
+                    .into());
+                }
+                Ok(start..=end)
+            })
+            .collect::<anyhow::Result<RangeSet>>()?;
+
+        // Overlapping ranges would count the IDs they share twice.
+        ranges.coalesce_ranges();
+
+        Ok(ranges)
+    }
 
-impl Challenge {
+    fn part_1(&self, ranges: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let total_of_invalid_ids: u128 = ranges
+            .iter()
+            .map(|range| sum_of_invalid_ids(range, false))
+            .sum();
+
+        Ok(total_of_invalid_ids.into())
+    }
+
+    fn part_2(&self, ranges: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let total_of_invalid_ids_any: u128 = ranges
+            .iter()
+            .map(|range| sum_of_invalid_ids(range, true))
+            .inspect(|sum| trace!("Invalid IDs sum to {sum}"))
+            .sum();
+
+        Ok(total_of_invalid_ids_any.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    const fn decimal_rsh(num: u64, digits: u32) -> u64 {
+        let pow10 = 10u64.pow(digits);
+        num / pow10
+    }
+
+    const fn decimal_lsh(num: u64, digits: u32) -> u64 {
+        let pow10 = 10u64.pow(digits);
+        num * pow10
+    }
+
+    fn decimal_substr(num: u64, start: u32, end: u32) -> u64 {
+        let num_digits = num.ilog10() + 1;
+        let lower = decimal_rsh(num, num_digits - end);
+        let upper = decimal_lsh(decimal_rsh(lower, end - start), end - start);
+
+        lower - upper
+    }
+
+    /// Checks a single ID for a block repeated twice, to test the enumeration against.
     fn invalid_id(id: u64) -> bool {
         let half_num_digits = (id.ilog10() + 1) / 2;
         let top_half = decimal_rsh(id, half_num_digits);
@@ -36,6 +178,8 @@ impl Challenge {
         top_half == bottom_half
     }
 
+    /// Checks a single ID for a block repeated any number of times, to test the enumeration
+    /// against.
     fn invalid_id_any(id: u64) -> bool {
         let num_digits = id.ilog10() + 1;
         for i in 1..=(num_digits / 2) {
@@ -55,32 +199,98 @@ impl Challenge {
 
         false
     }
-}
 
-impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
-        let re = Regex::new("(\\d+)-(\\d+),?").unwrap();
-
-        let (ids_1, ids_2) = re
-            .captures_iter(input)
-            .map(|m| m.extract::<2>().1)
-            .flat_map(|[start, end]| {
-                u64::from_str_radix(start, 10).unwrap()..u64::from_str_radix(end, 10).unwrap()
-            })
-            .tee();
-
-        let total_of_invalid_ids: u64 = ids_1.filter(|id| Self::invalid_id(*id)).sum();
-        let total_of_invalid_ids_any: u64 = ids_2
-            .filter(|id| Self::invalid_id_any(*id))
-            .map(|id| {
-                println!("{id}");
-                id
-            })
-            .sum();
+    #[test]
+    fn example() {
+        check_example(Challenge, 2, "example");
+    }
 
-        Ok(AOCResult {
-            part_1: total_of_invalid_ids.to_string(),
-            part_2: total_of_invalid_ids_any.to_string(),
-        })
+    #[test]
+    fn decimal_substr_takes_digits_from_the_left() {
+        assert_eq!(decimal_substr(123456, 0, 2), 12);
+        assert_eq!(decimal_substr(123456, 1, 3), 23);
+        assert_eq!(decimal_substr(123456, 4, 6), 56);
+        assert_eq!(decimal_substr(100200, 2, 4), 2);
+    }
+
+    #[test]
+    fn invalid_ids() {
+        assert!(invalid_id(6464));
+        assert!(!invalid_id(6465));
+        assert!(invalid_id_any(123123123));
+        assert!(invalid_id_any(1111111));
+        assert!(!invalid_id_any(1231234));
+    }
+
+    #[test]
+    fn enumeration_matches_checking_every_id() {
+        let ranges = [
+            1..=2000,
+            95..=115,
+            998..=1012,
+            222220..=222224,
+            1188511880..=1188511890,
+        ];
+
+        for range in ranges {
+            for any_repetitions in [false, true] {
+                let checked = range
+                    .clone()
+                    .filter(|&id| match any_repetitions {
+                        false => invalid_id(id),
+                        true => invalid_id_any(id),
+                    })
+                    .map(u128::from)
+                    .sum::<u128>();
+
+                assert_eq!(
+                    sum_of_invalid_ids(&range, any_repetitions),
+                    checked,
+                    "{range:?}, any repetitions: {any_repetitions}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wide_ranges() {
+        // Every 20-digit ID up to u64::MAX repeats a 10-digit block, from 1000000000 up to
+        // 1844674407, so the sum is 10000000001 times the sum of the blocks.
+        let (first, last) = (1_000_000_000u128, 1_844_674_407u128);
+        let expected = 10_000_000_001 * (first + last) * (last - first + 1) / 2;
+
+        assert_eq!(
+            sum_of_invalid_ids(&(10_000_000_000_000_000_000..=u64::MAX), false),
+            expected
+        );
+    }
+
+    #[test]
+    fn overlapping_ranges_count_once() {
+        let ranges = Challenge.parse("11-22,20-33,\n").unwrap();
+
+        assert_eq!(ranges.iter().collect::<Vec<_>>(), [&(11..=33)]);
+        assert_eq!(
+            Challenge.part_1(&ranges).unwrap(),
+            Answer::from(11 + 22 + 33)
+        );
+    }
+
+    #[test]
+    fn malformed_ranges() {
+        let error = |input| Challenge.parse(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error("11-22,95"),
+            "line 1, column 7: expected a range like `11-22`, found `95`"
+        );
+        assert_eq!(
+            error("11-2x"),
+            "line 1, column 4: expected an ID that fits in 64 bits, found `2x`"
+        );
+        assert_eq!(
+            error("22-11"),
+            "line 1, column 1: expected a range that doesn't end before it starts, found `22-11`"
+        );
     }
 }
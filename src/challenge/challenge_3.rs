@@ -1,7 +1,7 @@
-use anyhow::{Ok, anyhow};
-use itertools::Itertools;
+use log::trace;
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{ParseError, char_slices, parse_at};
 
 fn max_single_digit(arr: &[u8]) -> Option<(usize, u8)> {
     let mut max = None;
@@ -24,6 +24,11 @@ fn max_single_digit(arr: &[u8]) -> Option<(usize, u8)> {
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(3, "Lobby");
+
+/// The most batteries either part turns on in a bank.
+const MAX_TO_TURN_ON: usize = 12;
+
 impl Challenge {
     fn largest_joltage_2(batteries: &[u8]) -> u64 {
         let (first_digit_loc, first_digit) =
@@ -38,7 +43,7 @@ impl Challenge {
         let mut startpoint = 0;
         let mut string = 0;
         for i in (0..to_turn_on).rev() {
-            println!(">> {startpoint}");
+            trace!("Searching from {startpoint}");
             let (digit_loc, digit) =
                 max_single_digit(&batteries[startpoint..(batteries.len() - i)]).unwrap();
 
@@ -51,28 +56,72 @@ impl Challenge {
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
-        let (batteries_1, batteries_2) = input
+    type Input<'a> = Vec<Vec<u8>>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+        input
             .lines()
             .map(|line| {
-                line.chars()
-                    .map(|ch| ch.to_digit(10).map(|d| d as u8).ok_or(()))
-                    .collect::<Result<Vec<_>, ()>>()
-                    .unwrap()
+                if line.len() < MAX_TO_TURN_ON {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        format!("a bank of at least {MAX_TO_TURN_ON} joltage digits"),
+                    ));
+                }
+
+                char_slices(line)
+                    .map(|battery| parse_at(input, battery, "a joltage digit"))
+                    .collect::<Result<Vec<_>, _>>()
             })
-            .tee();
+            .collect::<Result<_, _>>()
+            .map_err(Into::into)
+    }
 
-        let largest_joltage_2: u64 = batteries_1
+    fn part_1(&self, banks: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let largest_joltage_2: u64 = banks
+            .iter()
             .map(|batteries| Self::largest_joltage(batteries.as_slice(), 2))
             .sum();
 
-        let largest_joltage_12: u64 = batteries_2
-            .map(|batteries| Self::largest_joltage(batteries.as_slice(), 12))
+        Ok(largest_joltage_2.into())
+    }
+
+    fn part_2(&self, banks: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let largest_joltage_12: u64 = banks
+            .iter()
+            .map(|batteries| Self::largest_joltage(batteries.as_slice(), MAX_TO_TURN_ON))
             .sum();
 
-        Ok(AOCResult {
-            part_1: largest_joltage_2.to_string(),
-            part_2: largest_joltage_12.to_string(),
-        })
+        Ok(largest_joltage_12.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example() {
+        check_example(Challenge, 3, "example");
+    }
+
+    #[test]
+    fn malformed_banks() {
+        let error = |input| Challenge.parse(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error("987654321111111\n\n"),
+            "line 2, column 1: expected a bank of at least 12 joltage digits, found nothing"
+        );
+        assert_eq!(
+            error("987654321111111\n9"),
+            "line 2, column 1: expected a bank of at least 12 joltage digits, found `9`"
+        );
+        assert_eq!(
+            error("98765432111x111"),
+            "line 1, column 12: expected a joltage digit, found `x`"
+        );
     }
 }
@@ -1,48 +1,55 @@
-use anyhow::{Result, anyhow};
-use ndarray::{Array2, s};
+use log::trace;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{Grid, GridTile};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Tile {
     Empty,
     Paper,
 }
 
+impl GridTile for Tile {
+    fn to_char(&self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Paper => '@',
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(4, "Printing Department");
+
 impl Challenge {
-    fn get_accessible_rolls(arr: &Array2<Tile>) -> Vec<(usize, usize)> {
-        let accessible_rolls = arr
+    fn get_accessible_rolls(grid: &Grid<Tile>) -> Vec<(usize, usize)> {
+        let accessible_rolls = grid
             .indexed_iter()
             .par_bridge()
-            .filter_map(|(idx, tile)| {
-                if *tile == Tile::Empty {
-                    None
-                } else {
-                    let r0 = usize::saturating_sub(idx.0, 1);
-                    let c0 = usize::saturating_sub(idx.1, 1);
-                    let r1 = usize::min(idx.0 + 1, arr.shape()[0] - 1);
-                    let c1 = usize::min(idx.1 + 1, arr.shape()[1] - 1);
-
-                    let window = arr.slice(s![r0..=r1, c0..=c1]);
-
-                    (window.iter().filter(|i| **i == Tile::Paper).count() < 5).then_some(idx)
-                }
+            .filter(|(_, tile)| **tile == Tile::Paper)
+            .filter_map(|(idx, _)| {
+                let neighbouring_rolls = grid
+                    .neighbours8(idx)
+                    .filter(|pos| grid[*pos] == Tile::Paper)
+                    .count();
+
+                (neighbouring_rolls < 4).then_some(idx)
             })
             .collect::<Vec<_>>();
 
         accessible_rolls
     }
 
-    fn remove_rolls(arr: &mut Array2<Tile>) -> Option<usize> {
-        let accessible_rolls = Self::get_accessible_rolls(arr);
+    fn remove_rolls(grid: &mut Grid<Tile>) -> Option<usize> {
+        let accessible_rolls = Self::get_accessible_rolls(grid);
 
         accessible_rolls
             .iter()
-            .for_each(|idx| arr[*idx] = Tile::Empty);
+            .for_each(|idx| grid[*idx] = Tile::Empty);
+        trace!("Removed {} rolls:\n{grid}", accessible_rolls.len());
 
         match accessible_rolls.len() {
             0 => None,
@@ -52,30 +59,39 @@ impl Challenge {
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
-        let rows = input.lines().count();
-        let cols = input.lines().next().unwrap().chars().count();
-
-        let tiles_flat = input
-            .lines()
-            .flat_map(|line| {
-                line.chars().map(|ch| match ch {
-                    '@' => Tile::Paper,
-                    _ => Tile::Empty,
-                })
-            })
-            .collect::<Vec<_>>();
+    type Input<'a> = Grid<Tile>;
 
-        let mut tiles = Array2::from_shape_vec((rows, cols), tiles_flat)
-            .map_err(|_| anyhow!("Couldn't construct array"))?;
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+        let tile = |ch| match ch {
+            '@' => Some(Tile::Paper),
+            '.' => Some(Tile::Empty),
+            _ => None,
+        };
 
-        let num_accessible_rolls = Self::get_accessible_rolls(&tiles).len();
+        Ok(Grid::parse(input, tile, "`@` or `.`")?)
+    }
 
+    fn part_1(&self, tiles: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let num_accessible_rolls = Self::get_accessible_rolls(tiles).len();
+
+        Ok(num_accessible_rolls.into())
+    }
+
+    fn part_2(&self, tiles: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let mut tiles = tiles.clone();
         let num_removed_rolls: usize = (0..).map_while(|_| Self::remove_rolls(&mut tiles)).sum();
 
-        Ok(AOCResult {
-            part_1: num_accessible_rolls.to_string(),
-            part_2: num_removed_rolls.to_string(),
-        })
+        Ok(num_removed_rolls.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example() {
+        check_example(Challenge, 4, "example");
     }
 }
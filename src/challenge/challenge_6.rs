@@ -1,9 +1,11 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use itertools::Itertools;
+use log::trace;
 use ndarray::{Array2, Axis};
 use rayon::{iter::ParallelIterator, str::ParallelString};
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{ParseError, Ragged, char_slices, ragged_to_arr};
 
 #[derive(Debug, Clone, Copy)]
 enum Op {
@@ -11,9 +13,16 @@ enum Op {
     Mul,
 }
 
+pub struct Worksheet<'a> {
+    lines: Vec<&'a str>,
+    ops: Vec<Op>,
+}
+
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(6, "Trash Compactor");
+
 impl Challenge {
     fn parse_table_p1<'a>(
         lines: impl Iterator<Item = &'a str>,
@@ -27,13 +36,13 @@ impl Challenge {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let numbers = crate::utils::ragged_to_arr(table)?;
+        let numbers = ragged_to_arr(table, Ragged::Reject)?;
 
         let grand_total: u64 = numbers
             .axis_iter(ndarray::Axis(1))
             .zip(ops)
             .map(|(ax, op)| {
-                println!("{ax:?} {op:?}");
+                trace!("{ax:?} {op:?}");
                 match op {
                     Op::Add => ax.sum(),
                     Op::Mul => ax.product(),
@@ -51,7 +60,8 @@ impl Challenge {
         let chars_ragged = lines
             .map(|line| line.chars().collect::<Vec<_>>())
             .collect::<Vec<_>>();
-        let chars_arr = crate::utils::ragged_to_arr(chars_ragged)?;
+        // Editors often strip the trailing spaces that keep the columns lined up.
+        let chars_arr = ragged_to_arr(chars_ragged, Ragged::AlignLeft(' '))?;
 
         let groups = chars_arr
             .t()
@@ -68,7 +78,7 @@ impl Challenge {
         let grand_total: u64 = table
             .zip(ops)
             .map(|(ax, op)| -> u64 {
-                println!("{ax:?} {op:?}");
+                trace!("{ax:?} {op:?}");
                 match op {
                     Op::Add => ax.iter().sum(),
                     Op::Mul => ax.iter().product(),
@@ -81,29 +91,67 @@ impl Challenge {
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> Result<AOCResult> {
+    type Input<'a> = Worksheet<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
         let mut lines = input.lines();
 
         let ops = lines
             .by_ref()
             .next_back()
-            .ok_or(anyhow!("No last line"))?
+            .ok_or_else(|| ParseError::at_end(input, "a line of operations"))?
             .split_whitespace()
             .map(|op| match op {
                 "+" => Ok(Op::Add),
                 "*" => Ok(Op::Mul),
-                _ => Err(anyhow!("Invalid operation")),
+                _ => Err(ParseError::at(input, op, "`+` or `*`")),
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let lines = lines.collect::<Vec<_>>();
+        if let Some(cell) = lines
+            .iter()
+            .copied()
+            .flat_map(char_slices)
+            .find(|cell| !matches!(cell.as_bytes(), [b'0'..=b'9' | b' ']))
+        {
+            return Err(ParseError::at(input, cell, "a digit or a space").into());
+        }
+
+        Ok(Worksheet { lines, ops })
+    }
+
+    fn part_1(&self, worksheet: &Self::Input<'_>) -> Result<Answer> {
+        let grand_total_1 = Self::parse_table_p1(
+            worksheet.lines.iter().copied(),
+            worksheet.ops.iter().copied(),
+        )?;
 
-        let (lines_1, lines_2) = lines.tee();
+        Ok(grand_total_1.into())
+    }
+
+    fn part_2(&self, worksheet: &Self::Input<'_>) -> Result<Answer> {
+        let grand_total_2 = Self::parse_table_p2(
+            worksheet.lines.iter().copied(),
+            worksheet.ops.iter().copied(),
+        )?;
 
-        let grand_total_1 = Self::parse_table_p1(lines_1, ops.iter().copied())?;
-        let grand_total_2 = Self::parse_table_p2(lines_2, ops.into_iter())?;
+        Ok(grand_total_2.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    fn example() {
+        check_example(Challenge, 6, "example");
+    }
 
-        Ok(AOCResult {
-            part_1: grand_total_1.to_string(),
-            part_2: grand_total_2.to_string(),
-        })
+    #[test]
+    fn example_without_trailing_spaces() {
+        check_example(Challenge, 6, "example_stripped");
     }
 }
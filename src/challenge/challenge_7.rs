@@ -3,17 +3,20 @@ use std::collections::VecDeque;
 use anyhow::{Result, anyhow};
 use fxhash::FxHashMap;
 use itertools::Itertools;
+use log::debug;
 use ndarray::{Array2, Axis, Ix2, s};
 
-use crate::utils::ragged_to_arr;
+use crate::utils::Grid;
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
 
 #[derive(Debug, Default)]
 pub struct Challenge;
 
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(7, "Laboratories");
+
 impl Challenge {
-    fn trace_beam_split(map: &Array2<char>) -> u64 {
+    fn trace_beam_split(map: &Grid<char>) -> u64 {
         let mut map = map.clone();
         let mut rows = map.axis_iter_mut(Axis(0));
 
@@ -42,12 +45,12 @@ impl Challenge {
             source = dest;
         }
 
-        println!("{}", map);
+        debug!("Beams:\n{map}");
 
         splits
     }
 
-    fn trace_beam_choose(map: &Array2<char>) -> u64 {
+    fn trace_beam_choose(map: &Grid<char>) -> u64 {
         let mut choices_map: Array2<Option<u64>> =
             Array2::from_shape_fn(map.raw_dim(), |(row, col)| {
                 if row == map.shape()[0] - 1 {
@@ -72,29 +75,44 @@ impl Challenge {
             }
         }
 
-        println!("{choices_map:?}");
+        debug!("Choices: {choices_map:?}");
 
         choices_map[Ix2(0, choices_map.shape()[1] / 2)].unwrap()
     }
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> Result<AOCResult> {
-        let chars_ragged = input
-            .lines()
-            .map(|line| line.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+    type Input<'a> = Grid<char>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let tile = |ch| matches!(ch, 'S' | '.' | '^').then_some(ch);
+        let map = Grid::parse(input, tile, "`S`, `.` or `^`")?;
+
+        debug!("Map:\n{map}");
+
+        Ok(map)
+    }
 
-        let map = ragged_to_arr(chars_ragged)?;
+    fn part_1(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        let splits = Self::trace_beam_split(map);
 
-        println!("{map:?}");
+        Ok(splits.into())
+    }
+
+    fn part_2(&self, map: &Self::Input<'_>) -> Result<Answer> {
+        let choices = Self::trace_beam_choose(map);
+
+        Ok(choices.into())
+    }
+}
 
-        let splits = Self::trace_beam_split(&map);
-        let choices = Self::trace_beam_choose(&map);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::check_example;
 
-        Ok(AOCResult {
-            part_1: splits.to_string(),
-            part_2: choices.to_string(),
-        })
+    #[test]
+    fn example() {
+        check_example(Challenge, 7, "example");
     }
 }
@@ -6,12 +6,36 @@ use itertools::Itertools;
 use nalgebra::{Point3, distance, point};
 use ndarray::prelude::*;
 
-use super::{AOCChallenge, AOCResult};
+use super::{AOCChallenge, Answer, ChallengeInfo};
+use crate::utils::{ParseError, parse_at};
 
-#[derive(Debug, Default)]
-pub struct Challenge;
+#[derive(Debug)]
+pub struct Challenge {
+    /// How many of the closest pairs to connect in part 1.
+    connections: usize,
+}
+
+impl Default for Challenge {
+    fn default() -> Self {
+        Self { connections: 1000 }
+    }
+}
+
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>(8, "Playground");
 
 impl Challenge {
+    /// Pairs of junctions, closest first.
+    fn sorted_pairs(junctions: &[Point3<f32>]) -> impl Iterator<Item = (usize, usize)> {
+        let pairs = junctions
+            .iter()
+            .enumerate()
+            .tuple_combinations()
+            .map(|((ai, a), (bi, b))| ((ai, bi), distance(a, b)));
+
+        lazysort::SortedBy::sorted_by(pairs, |(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+            .map(|((a, b), _)| (a, b))
+    }
+
     fn connect(num_nodes: usize, adj_table: &Array2<bool>, start_point: usize) -> FxHashSet<usize> {
         let mut visited = FxHashSet::default();
         let mut to_visit: VecDeque<usize> = VecDeque::from([start_point]);
@@ -88,45 +112,37 @@ impl Challenge {
 }
 
 impl AOCChallenge for Challenge {
-    fn run(self, input: &str) -> anyhow::Result<AOCResult> {
+    type Input<'a> = Vec<Point3<f32>>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
         let re = regex::Regex::new("(\\d+),(\\d+),(\\d+)")?;
-        let junctions: Vec<Point3<f32>> = input
+        input
             .lines()
-            .map(|line| {
-                re.captures_iter(line)
+            .map(|line| -> Result<_> {
+                let [x, y, z] = re
+                    .captures_iter(line)
                     .exactly_one()
-                    .expect("invalid format")
+                    .map_err(|_| ParseError::at(input, line, "a position like `162,817,812`"))?
                     .extract::<3>()
-                    .1
-            })
-            .map(|[x, y, z]| -> Result<_> {
-                Ok(point![
-                    x.parse::<f32>()?,
-                    y.parse::<f32>()?,
-                    z.parse::<f32>()?,
-                ])
-            })
-            .collect::<Result<Vec<_>>>()?;
+                    .1;
+                let coord = |coord: &str| parse_at::<f32>(input, coord, "a coordinate");
 
-        let pairs = junctions
-            .iter()
-            .enumerate()
-            .tuple_combinations()
-            .map(|((ai, a), (bi, b))| ((ai, bi), distance(&a, &b)));
+                Ok(point![coord(x)?, coord(y)?, coord(z)?])
+            })
+            .collect::<Result<Vec<_>>>()
+    }
 
+    fn part_1(&self, junctions: &Self::Input<'_>) -> anyhow::Result<Answer> {
         let mut adj_table: Array2<bool> = Array2::default((junctions.len(), junctions.len()));
 
-        let mut sorted_pairs =
-            lazysort::SortedBy::sorted_by(pairs, |(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
-                .map(|((a, b), d)| (a, b));
+        Self::sorted_pairs(junctions)
+            .take(self.connections)
+            .for_each(|(a, b)| {
+                adj_table[Ix2(a, b)] = true;
+                adj_table[Ix2(b, a)] = true;
+            });
 
-        sorted_pairs.by_ref().take(1000).for_each(|(a, b)| {
-            adj_table[Ix2(a, b)] = true;
-            adj_table[Ix2(b, a)] = true;
-        });
-
-        let (components, component_map) =
-            Self::get_connected_components(junctions.len(), &adj_table);
+        let (components, _) = Self::get_connected_components(junctions.len(), &adj_table);
 
         let largest_components: usize = components
             .iter()
@@ -135,19 +151,47 @@ impl AOCChallenge for Challenge {
             .take(3)
             .product();
 
+        Ok(largest_components.into())
+    }
+
+    fn part_2(&self, junctions: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        let mut adj_table: Array2<bool> = Array2::default((junctions.len(), junctions.len()));
+
+        let (components, component_map) =
+            Self::get_connected_components(junctions.len(), &adj_table);
+
         let (last_a, last_b) = Self::update_connected_components_until_all_connected(
             junctions.len(),
             &mut adj_table,
             components,
             component_map,
-            sorted_pairs,
+            Self::sorted_pairs(junctions),
         );
 
         let last_product = (junctions[last_a].x as u64) * (junctions[last_b].x as u64);
 
-        Ok(AOCResult {
-            part_1: largest_components.to_string(),
-            part_2: last_product.to_string(),
-        })
+        Ok(last_product.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::test_utils::{check_example, fixture_dir};
+
+    #[test]
+    fn example() {
+        check_example(Challenge::default(), 8, "example");
+    }
+
+    /// The example's part 1 answer only makes 10 connections rather than 1000, so it's left out
+    /// of the fixture's answers, which `aoc run` checks with the registered challenge.
+    #[test]
+    fn example_with_ten_connections() {
+        let input = std::fs::read_to_string(fixture_dir(8).join("example.txt")).unwrap();
+        let challenge = Challenge { connections: 10 };
+        let junctions = challenge.parse(&input).unwrap();
+
+        assert_eq!(challenge.part_1(&junctions).unwrap(), 40.into());
     }
 }
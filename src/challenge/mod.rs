@@ -1,45 +1,100 @@
-use std::{fmt::Display, io::Read, path::Path};
+use std::{fmt::Display, str::FromStr};
 
 use anyhow::Result;
 
-mod challenge_1;
-mod challenge_10;
-mod challenge_11;
-mod challenge_12;
-mod challenge_2;
-mod challenge_3;
-mod challenge_4;
-mod challenge_5;
-mod challenge_6;
-mod challenge_7;
-mod challenge_8;
-mod challenge_9;
-
-pub use challenge_1::Challenge as Challenge1;
-pub use challenge_2::Challenge as Challenge2;
-pub use challenge_3::Challenge as Challenge3;
-pub use challenge_4::Challenge as Challenge4;
-pub use challenge_5::Challenge as Challenge5;
-pub use challenge_6::Challenge as Challenge6;
-pub use challenge_7::Challenge as Challenge7;
-pub use challenge_8::Challenge as Challenge8;
-pub use challenge_9::Challenge as Challenge9;
-pub use challenge_10::Challenge as Challenge10;
-pub use challenge_11::Challenge as Challenge11;
-pub use challenge_12::Challenge as Challenge12;
+use crate::logging::DayScope;
 
+mod answer;
+#[cfg(test)]
+pub mod test_utils;
+
+pub use answer::Answer;
+
+macro_rules! challenges {
+    ( $($module:ident),+ $(,)? ) => {
+        $(pub mod $module;)+
+
+        /// Every registered challenge, in the order they were listed.
+        pub const CHALLENGES: &[ChallengeInfo] = &[$($module::INFO),+];
+    };
+}
+
+// Listing a day's module here declares it and adds its `INFO` to `CHALLENGES`, so this is the
+// only place a new day has to be registered. `aoc new` adds to the list itself, through
+// `scaffold::register`, which expects one module per line.
+challenges!(
+    challenge_1,
+    challenge_2,
+    challenge_3,
+    challenge_4,
+    challenge_5,
+    challenge_6,
+    challenge_7,
+    challenge_8,
+    challenge_9,
+    challenge_10,
+    challenge_11,
+    challenge_12,
+);
+
+/// Looks up the registered challenge for a day.
+pub fn get(day: u8) -> Option<&'static ChallengeInfo> {
+    CHALLENGES.iter().find(|info| info.day == day)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part `{s}`, expected 1 or 2")),
+        }
+    }
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AOCResult {
-    part_1: String,
-    part_2: String,
+    part_1: Answer,
+    part_2: Answer,
 }
 
 impl AOCResult {
-    pub fn new(part_1: impl Into<String>, part_2: impl Into<String>) -> AOCResult {
+    pub fn new(part_1: impl Into<Answer>, part_2: impl Into<Answer>) -> AOCResult {
         AOCResult {
             part_1: part_1.into(),
             part_2: part_2.into(),
         }
     }
+
+    pub fn part(&self, part: Part) -> &Answer {
+        match part {
+            Part::One => &self.part_1,
+            Part::Two => &self.part_2,
+        }
+    }
 }
 
 impl Display for AOCResult {
@@ -49,14 +104,106 @@ impl Display for AOCResult {
 }
 
 pub trait AOCChallenge {
-    fn run(self, input: &str) -> Result<AOCResult>;
+    /// The parsed puzzle input, shared by both parts.
+    type Input<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>>;
+
+    fn part_1(&self, _input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn part_2(&self, _input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+}
+
+/// An object-safe view of an [`AOCChallenge`], so challenges with different input types can live
+/// in the same registry.
+pub trait DynChallenge {
+    fn prepare<'a>(&'a self, input: &'a str) -> Result<Box<dyn Prepared + 'a>>;
+}
+
+/// A challenge whose input has already been parsed, ready to run either part.
+pub trait Prepared {
+    fn run_part(&self, part: Part) -> Result<Answer>;
+}
+
+struct ParsedChallenge<'a, C: AOCChallenge> {
+    challenge: &'a C,
+    input: C::Input<'a>,
+}
+
+impl<C: AOCChallenge> DynChallenge for C {
+    fn prepare<'a>(&'a self, input: &'a str) -> Result<Box<dyn Prepared + 'a>> {
+        Ok(Box::new(ParsedChallenge {
+            challenge: self,
+            input: self.parse(input)?,
+        }))
+    }
+}
+
+impl<C: AOCChallenge> Prepared for ParsedChallenge<'_, C> {
+    fn run_part(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.challenge.part_1(&self.input),
+            Part::Two => self.challenge.part_2(&self.input),
+        }
+    }
+}
 
-    fn run_file(self, path: &Path) -> Result<AOCResult>
-    where
-        Self: Sized,
-    {
-        let file: String = std::fs::read_to_string(path)?;
+/// Describes a challenge so it can be discovered through [`CHALLENGES`].
+pub struct ChallengeInfo {
+    pub day: u8,
+    pub title: &'static str,
+    pub constructor: fn() -> Box<dyn DynChallenge>,
+}
+
+impl ChallengeInfo {
+    pub const fn new<C: AOCChallenge + Default + 'static>(day: u8, title: &'static str) -> Self {
+        Self {
+            day,
+            title,
+            constructor: || Box::new(C::default()),
+        }
+    }
 
-        self.run(file.as_str())
+    pub fn run(&self, input: &str) -> Result<AOCResult> {
+        let _scope = DayScope::enter(self.day);
+        let challenge = (self.constructor)();
+        let prepared = challenge.prepare(input)?;
+
+        Ok(AOCResult::new(
+            prepared.run_part(Part::One)?,
+            prepared.run_part(Part::Two)?,
+        ))
+    }
+
+    pub fn run_part(&self, input: &str, part: Part) -> Result<Answer> {
+        let _scope = DayScope::enter(self.day);
+        (self.constructor)().prepare(input)?.run_part(part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_unique_and_in_order() {
+        assert!(CHALLENGES.windows(2).all(|pair| pair[0].day < pair[1].day));
+    }
+
+    #[test]
+    fn every_challenge_has_an_example() {
+        for info in CHALLENGES {
+            let has_example = std::fs::read_dir(test_utils::fixture_dir(info.day))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .any(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"));
+
+            assert!(has_example, "Day {} has no example fixture", info.day);
+        }
     }
 }
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use super::{AOCChallenge, DynChallenge, Part};
+use crate::answers::ExpectedAnswers;
+
+pub fn fixture_dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("challenge_{day}"))
+}
+
+/// Runs `fixtures/challenge_<day>/<name>.txt` through a challenge and checks every part that has
+/// an answer in `<name>.toml`.
+pub fn check_example<C: AOCChallenge>(challenge: C, day: u8, name: &str) {
+    let dir = fixture_dir(day);
+    let input = std::fs::read_to_string(dir.join(format!("{name}.txt")))
+        .unwrap_or_else(|error| panic!("Couldn't read fixture {name} for day {day}: {error}"));
+    let expected = ExpectedAnswers::load(&dir.join(format!("{name}.toml"))).unwrap();
+
+    let prepared = challenge.prepare(&input).unwrap();
+
+    let checked = [Part::One, Part::Two]
+        .into_iter()
+        .filter_map(|part| Some((part, expected.part(part)?)))
+        .map(|(part, expected)| {
+            let answer = prepared.run_part(part).unwrap();
+            assert_eq!(&answer, expected, "day {day} part {part} on {name}");
+        })
+        .count();
+
+    assert!(checked > 0, "Fixture {name} for day {day} has no answers");
+}
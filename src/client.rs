@@ -0,0 +1,503 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use log::{debug, info};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::{Answer, Part};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2025;
+
+const USER_AGENT: &str = "github.com/william-gooch/advent-of-code-2025";
+
+pub fn default_config_path() -> PathBuf {
+    PathBuf::from(".aoc.toml")
+}
+
+/// Settings for talking to the puzzle server, read from a TOML file like:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8080"
+/// ```
+///
+/// The `AOC_SESSION` and `AOC_BASE_URL` environment variables take precedence over the file.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+impl Config {
+    /// Loads the config from a file, treating a missing file as empty, then applies any
+    /// environment overrides.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut config: Self = match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Couldn't parse config file {}", path.display()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => Self::default(),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("Couldn't read config file {}", path.display()));
+            }
+        };
+
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+
+        Ok(config)
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+}
+
+/// A logged-in connection to the puzzle server.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self> {
+        let session = config.session.clone().ok_or(anyhow!(
+            "No session token; set `session` in {} or the AOC_SESSION environment variable",
+            default_config_path().display()
+        ))?;
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: config.base_url().to_string(),
+            session,
+        })
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{YEAR}/day/{day}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input for a day.
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = format!("{}/input", self.day_url(day));
+        info!("Fetching {url}");
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(status, response) => anyhow!(
+                    "The server refused the input for day {day} ({status}): {}",
+                    response.into_string().unwrap_or_default().trim()
+                ),
+                error => anyhow!(error),
+            })?;
+
+        response
+            .into_string()
+            .with_context(|| format!("Couldn't read the input for day {day}"))
+    }
+
+    /// Submits an answer to one part of a day and reports the server's verdict.
+    pub fn submit(&self, day: u8, part: Part, answer: &Answer) -> Result<Outcome> {
+        Outcome::parse(&self.post_answer(day, part, answer)?)
+    }
+
+    /// Posts an answer, returning the page the server sends back without reading its verdict.
+    pub fn post_answer(&self, day: u8, part: Part, answer: &Answer) -> Result<String> {
+        let url = format!("{}/answer", self.day_url(day));
+        info!("Submitting {answer} for part {part} to {url}");
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])
+            .map_err(|error| match error {
+                ureq::Error::Status(status, _) => {
+                    anyhow!("The server refused the answer for day {day} ({status})")
+                }
+                error => anyhow!(error),
+            })?;
+
+        response
+            .into_string()
+            .context("Couldn't read the server's response")
+    }
+}
+
+/// Which way a wrong answer was off, when the server says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+        /// How long the server wants before the next guess.
+        wait_secs: Option<u64>,
+    },
+    /// The answer wasn't checked because the last one was too recent.
+    TooSoon {
+        wait_secs: Option<u64>,
+    },
+    /// The part is already solved, or isn't unlocked yet.
+    WrongLevel,
+    /// The answer was sent, but the server's response couldn't be understood.
+    Unknown,
+}
+
+impl Outcome {
+    /// Reads the verdict out of the page the server sends back.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            let hint = if page.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            Ok(Outcome::Incorrect {
+                hint,
+                wait_secs: parse_wait(page),
+            })
+        } else if page.contains("You gave an answer too recently") {
+            Ok(Outcome::TooSoon {
+                wait_secs: parse_wait(page),
+            })
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::WrongLevel)
+        } else {
+            debug!("Unrecognised response: {page}");
+            bail!("Couldn't understand the server's response to the answer")
+        }
+    }
+
+    /// How long to wait before submitting another answer, if the server said.
+    pub fn wait_secs(&self) -> Option<u64> {
+        match self {
+            Outcome::Incorrect { wait_secs, .. } | Outcome::TooSoon { wait_secs } => *wait_secs,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect { hint: None, .. } => write!(f, "incorrect"),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => write!(f, "incorrect, too high"),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => write!(f, "incorrect, too low"),
+            Outcome::TooSoon { wait_secs: None } => write!(f, "submitted too soon"),
+            Outcome::TooSoon {
+                wait_secs: Some(secs),
+            } => write!(f, "submitted too soon, {secs}s left to wait"),
+            Outcome::WrongLevel => write!(f, "not the current part; is it already solved?"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Finds how long the server says to wait, from either "You have 1m 30s left to wait" or
+/// "please wait 5 minutes before trying again".
+fn parse_wait(page: &str) -> Option<u64> {
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let before = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+
+    if let Some(captures) = left.captures(page) {
+        let mins = captures
+            .get(1)
+            .map_or(Some(0), |m| m.as_str().parse().ok())?;
+        let secs: u64 = captures[2].parse().ok()?;
+        Some(mins * 60 + secs)
+    } else if let Some(captures) = before.captures(page) {
+        let mins: u64 = match &captures[1] {
+            "one" => 1,
+            mins => mins.parse().ok()?,
+        };
+        Some(mins * 60)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// The input was already on disk, so the server wasn't asked for it again.
+    AlreadyCached,
+}
+
+/// Makes sure the input for a day is at `path`, downloading it if it isn't there yet.
+pub fn fetch_input(config: &Config, day: u8, path: &Path) -> Result<Fetched> {
+    if path.exists() {
+        return Ok(Fetched::AlreadyCached);
+    }
+
+    let input = Client::new(config)?.input(day)?;
+    if input.is_empty() {
+        bail!("The server sent an empty input for day {day}");
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Couldn't create directory {}", dir.display()))?;
+    }
+    std::fs::write(path, input).with_context(|| format!("Couldn't write {}", path.display()))?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+pub mod test_server {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    /// A request as seen by the [`serve`] stand-in server.
+    #[derive(Debug)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serves one canned response to each of the next `responses.len()` requests on a local
+    /// port, returning the base URL and a handle that yields the requests it saw.
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap_or_default().to_string();
+                    let path = parts.next().unwrap_or_default().to_string();
+
+                    let mut cookie = None;
+                    let mut content_length = 0;
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end();
+                        if header.is_empty() {
+                            break;
+                        }
+
+                        let (name, value) = header.split_once(": ").unwrap();
+                        match name.to_ascii_lowercase().as_str() {
+                            "cookie" => cookie = Some(value.to_string()),
+                            "content-length" => content_length = value.parse().unwrap(),
+                            _ => {}
+                        }
+                    }
+
+                    let mut request_body = vec![0; content_length];
+                    reader.read_exact(&mut request_body).unwrap();
+
+                    let mut stream = reader.into_inner();
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    Request {
+                        method,
+                        path,
+                        cookie,
+                        body: String::from_utf8(request_body).unwrap(),
+                    }
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_url: &str) -> Config {
+        Config {
+            session: Some("abc123".to_string()),
+            base_url: Some(base_url.to_string()),
+        }
+    }
+
+    #[test]
+    fn fetch_downloads_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input/Challenge3.txt");
+        let (base_url, server) = test_server::serve(vec![(200, "987\n811\n".to_string())]);
+
+        assert_eq!(
+            fetch_input(&config(&base_url), 3, &path).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(
+            fetch_input(&config(&base_url), 3, &path).unwrap(),
+            Fetched::AlreadyCached
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "987\n811\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2025/day/3/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+    }
+
+    #[test]
+    fn fetch_reports_refusals() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Challenge3.txt");
+        let (base_url, server) = test_server::serve(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string(),
+        )]);
+
+        let error = fetch_input(&config(&base_url), 3, &path).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("(400): Puzzle inputs differ by user.")
+        );
+        assert!(!path.exists());
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn outcomes() {
+        let page = |text: &str| format!("<main><article><p>{text}</p></article></main>");
+
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+            .unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too high.  Please wait one minute \
+                 before trying again."
+            ))
+            .unwrap(),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait_secs: Some(60),
+            }
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            ))
+            .unwrap(),
+            Outcome::Incorrect {
+                hint: None,
+                wait_secs: Some(300),
+            }
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 4s left to wait."
+            ))
+            .unwrap(),
+            Outcome::TooSoon {
+                wait_secs: Some(64)
+            }
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Outcome::WrongLevel
+        );
+        assert!(Outcome::parse(&page("Something else entirely.")).is_err());
+    }
+
+    #[test]
+    fn submit_posts_the_answer() {
+        let (base_url, server) = test_server::serve(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>".to_string(),
+        )]);
+
+        let client = Client::new(&config(&base_url)).unwrap();
+        let outcome = client.submit(7, Part::Two, &Answer::from(40)).unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2025/day/7/answer");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        assert_eq!(requests[0].body, "level=2&answer=40");
+    }
+
+    #[test]
+    fn fetch_needs_a_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::default();
+
+        let error = fetch_input(&config, 3, &dir.path().join("Challenge3.txt")).unwrap_err();
+        assert!(error.to_string().starts_with("No session token"));
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    challenge::{Answer, Part},
+    client::{Hint, Outcome},
+};
+
+/// The least time to leave between any two submissions, whatever the server says.
+pub const MIN_SECS_BETWEEN_SUBMISSIONS: u64 = 30;
+
+pub fn default_history_path() -> PathBuf {
+    PathBuf::from("answers/history.json")
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+}
+
+/// Every answer submitted to the server, so known-wrong guesses aren't sent twice.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Loads the history from a file, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("Couldn't parse history file {}", path.display())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => {
+                Err(error).with_context(|| format!("Couldn't read history file {}", path.display()))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Couldn't create directory {}", dir.display()))?;
+        }
+
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Couldn't write history file {}", path.display()))
+    }
+
+    fn for_part(&self, day: u8, part: Part) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.day == day && submission.part == part.number())
+    }
+
+    /// Refuses answers that earlier submissions show can't be right, and submissions made before
+    /// the server's last requested wait is over or too soon after the last one.
+    pub fn check(&self, day: u8, part: Part, answer: &Answer, now: u64) -> Result<()> {
+        for submission in self.for_part(day, part) {
+            let Ok(previous) = submission.answer.parse::<Answer>();
+            let numbers = answer.as_i128().zip(previous.as_i128());
+
+            match &submission.outcome {
+                Outcome::Correct => {
+                    bail!("Part {part} of day {day} is already solved, with {previous}")
+                }
+                Outcome::Incorrect { .. } if previous == *answer => {
+                    bail!(
+                        "{answer} was already submitted for part {part} of day {day}, and was wrong"
+                    )
+                }
+                Outcome::Incorrect {
+                    hint: Some(Hint::TooHigh),
+                    ..
+                } if numbers.is_some_and(|(answer, previous)| answer >= previous) => {
+                    bail!("{answer} must be too high, since {previous} already was")
+                }
+                Outcome::Incorrect {
+                    hint: Some(Hint::TooLow),
+                    ..
+                } if numbers.is_some_and(|(answer, previous)| answer <= previous) => {
+                    bail!("{answer} must be too low, since {previous} already was")
+                }
+                _ => {}
+            }
+        }
+
+        let wait_until = self
+            .for_part(day, part)
+            .filter_map(|submission| {
+                Some(submission.submitted_at + submission.outcome.wait_secs()?)
+            })
+            .max();
+        if let Some(wait_until) = wait_until
+            && now < wait_until
+        {
+            bail!(
+                "The server asked for a wait; try again in {}s",
+                wait_until - now
+            );
+        }
+
+        let last_submitted_at = self
+            .submissions
+            .iter()
+            .map(|submission| submission.submitted_at)
+            .max();
+        if let Some(last_submitted_at) = last_submitted_at
+            && now < last_submitted_at + MIN_SECS_BETWEEN_SUBMISSIONS
+        {
+            bail!(
+                "Answers are sent at most once every {MIN_SECS_BETWEEN_SUBMISSIONS}s; try again in {}s",
+                last_submitted_at + MIN_SECS_BETWEEN_SUBMISSIONS - now
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer, outcome: Outcome, now: u64) {
+        self.submissions.push(Submission {
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            outcome,
+            submitted_at: now,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn incorrect(hint: Option<Hint>) -> Outcome {
+        Outcome::Incorrect {
+            hint,
+            wait_secs: Some(60),
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut history = History::default();
+        history.record(
+            1,
+            Part::One,
+            &Answer::from(500),
+            incorrect(Some(Hint::TooHigh)),
+            0,
+        );
+        history.record(
+            1,
+            Part::One,
+            &Answer::from(100),
+            incorrect(Some(Hint::TooLow)),
+            100,
+        );
+        history.record(1, Part::One, &Answer::from(300), incorrect(None), 200);
+
+        let check = |answer: i64| history.check(1, Part::One, &Answer::from(answer), 1000);
+
+        assert!(check(300).is_err());
+        assert!(check(500).is_err());
+        assert!(check(600).is_err());
+        assert!(check(100).is_err());
+        assert!(check(50).is_err());
+        assert!(check(250).is_ok());
+
+        assert!(
+            history
+                .check(1, Part::Two, &Answer::from(300), 1000)
+                .is_ok()
+        );
+        assert!(
+            history
+                .check(2, Part::One, &Answer::from(300), 1000)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut history = History::default();
+        history.record(3, Part::Two, &Answer::from("ABC"), Outcome::Correct, 0);
+
+        assert!(
+            history
+                .check(3, Part::Two, &Answer::from("XYZ"), 1000)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn waits_as_asked() {
+        let mut history = History::default();
+        history.record(1, Part::One, &Answer::from(300), incorrect(None), 1000);
+
+        let check = |now| history.check(1, Part::One, &Answer::from(301), now);
+
+        assert_eq!(
+            check(1030).unwrap_err().to_string(),
+            "The server asked for a wait; try again in 30s"
+        );
+        assert!(check(1060).is_ok());
+    }
+
+    #[test]
+    fn leaves_a_gap_between_submissions() {
+        let mut history = History::default();
+        history.record(1, Part::One, &Answer::from(300), Outcome::Correct, 1000);
+        history.record(2, Part::One, &Answer::from(7), Outcome::Unknown, 1010);
+
+        let check = |now| history.check(1, Part::Two, &Answer::from(301), now);
+
+        assert_eq!(
+            check(1020).unwrap_err().to_string(),
+            "Answers are sent at most once every 30s; try again in 20s"
+        );
+        assert!(check(1040).is_ok());
+        assert!(history.check(2, Part::One, &Answer::from(7), 1040).is_ok());
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers/history.json");
+
+        let mut history = History::load(&path).unwrap();
+        history.record(
+            1,
+            Part::One,
+            &Answer::from(300),
+            incorrect(Some(Hint::TooLow)),
+            5,
+        );
+        history.save(&path).unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert!(
+            history
+                .check(1, Part::One, &Answer::from(200), 1000)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Solutions to Advent of Code 2025, with the tools for running, checking and timing them.
+//!
+//! Every day's solver implements [`challenge::AOCChallenge`] and is listed in
+//! [`challenge::CHALLENGES`]. [`runner`] runs them and checks their answers, and [`utils`] holds
+//! the parsing and data structures they share.
+#![feature(cmp_minmax)]
+#![feature(substr_range)]
+
+pub mod answers;
+pub mod bench;
+pub mod challenge;
+pub mod client;
+pub mod history;
+pub mod logging;
+pub mod output;
+pub mod runner;
+pub mod scaffold;
+pub mod utils;
+pub mod watch;
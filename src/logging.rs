@@ -0,0 +1,59 @@
+use std::cell::Cell;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+thread_local! {
+    static DAY: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// Writes log records to stderr, so they never get mixed up with the answers on stdout.
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        match DAY.get() {
+            Some(day) => eprintln!("[{} day {day}] {}", record.level(), record.args()),
+            None => eprintln!("[{}] {}", record.level(), record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger. Warnings and errors are shown by default, each `-v` adds a level of
+/// detail, and `--quiet` leaves only errors.
+pub fn init(verbose: u8, quiet: bool) {
+    let level = match (quiet, verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    };
+
+    log::set_logger(&Logger).expect("Logger should only be installed once");
+    log::set_max_level(level);
+}
+
+/// Tags log records from this thread with the day being run, until dropped.
+pub struct DayScope(Option<u8>);
+
+impl DayScope {
+    pub fn enter(day: u8) -> Self {
+        Self(DAY.replace(Some(day)))
+    }
+}
+
+impl Drop for DayScope {
+    fn drop(&mut self) {
+        DAY.set(self.0);
+    }
+}
@@ -1,51 +1,362 @@
-#![feature(cmp_minmax)]
-#![feature(vec_from_fn)]
-use std::{env::args, path::Path};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
-use anyhow::anyhow;
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Parser, Subcommand};
+use log::error;
 
-use crate::challenge::*;
+use aoc::{
+    answers::{self, ExpectedAnswers},
+    bench,
+    challenge::{self, Answer, CHALLENGES, ChallengeInfo, Part},
+    client, history, logging,
+    output::{self, Format},
+    runner::{self, Status},
+    scaffold, watch,
+};
 
-mod challenge;
-mod utils;
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2025 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
 
-macro_rules! generate {
-    ( $opt_var:expr, $($i:ident),+ ) => {
-        match $opt_var {
-            $(stringify!($i) => $i::default().run_file(Path::new(format!("input/{}.txt", stringify!($i)).as_str())),)+
-            _ => Err(anyhow!("No such challenge exists."))
+    /// Log more detail to stderr; repeat for more (-v, -vv, -vvv).
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Only log errors.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// How to print results to stdout.
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the challenge for a single day.
+    Run {
+        /// The day to run.
+        day: u8,
+
+        /// Only run this part (1 or 2).
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// The input file, or `-` to read from stdin. Defaults to `input/Challenge<day>.txt`.
+        ///
+        /// A directory or glob (like `inputs/*.txt`) runs every input it names, checking each
+        /// against the `.toml` answers file next to it.
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// A TOML file of expected answers to check against. Defaults to `answers/<day>.toml`
+        /// when using the default input.
+        #[arg(long)]
+        answers: Option<PathBuf>,
+
+        /// Give up on the challenge after this many seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Run every challenge against its default input and print a summary table.
+    All {
+        /// Give up on each challenge after this many seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Time the parse step and each part of a challenge over repeated runs.
+    Bench {
+        /// The day to benchmark, or `all` for every day with an input.
+        target: Target,
+
+        /// How many times to run each challenge.
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Also write the results to this file as JSON.
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// List the days that have a challenge.
+    List,
+    /// Download the input for a day to `input/Challenge<day>.txt`, unless it's already there.
+    Fetch {
+        /// The day to fetch.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=12))]
+        day: u8,
+    },
+    /// Create the module, example fixture and answer stubs for a new day, and register it.
+    New {
+        /// The day to create.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The puzzle's title. Defaults to `Day <day>`.
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// Run one part of a day against its default input and submit the answer.
+    Submit {
+        /// The day to submit.
+        day: u8,
+
+        /// The part to submit (1 or 2).
+        part: Part,
+    },
+    /// Re-run a day whenever its input changes, showing how the answers changed.
+    Watch {
+        /// The day to watch.
+        day: u8,
+
+        /// The input file to run and watch. Defaults to `input/Challenge<day>.txt`.
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Also run and watch the examples in this directory, checking each against the answers
+        /// next to it. Defaults to `fixtures/challenge_<day>`.
+        #[arg(long)]
+        fixtures: Option<Option<PathBuf>>,
+
+        /// How often to check for changes, in seconds.
+        #[arg(long, default_value = "0.5", value_parser = parse_seconds)]
+        interval: Duration,
+
+        /// Give up on each run after this many seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Target::All),
+            _ => s
+                .parse()
+                .map(Target::Day)
+                .map_err(|_| format!("expected a day or `all`, found `{s}`")),
         }
     }
 }
 
-fn main() {
-    println!("Hello, world!");
-
-    let challenge_to_run = args()
-        .skip(1)
-        .next()
-        .expect("Needs at least one argument for challenge to run.");
-
-    println!("{:?}", challenge_to_run);
-
-    let output = generate!(
-        challenge_to_run.as_str(),
-        Challenge1,
-        Challenge2,
-        Challenge3,
-        Challenge4,
-        Challenge5,
-        Challenge6,
-        Challenge7,
-        Challenge8,
-        Challenge9,
-        Challenge10,
-        Challenge11,
-        Challenge12
-    );
-
-    match output {
-        Ok(output) => println!("{}", output),
-        Err(error) => eprintln!("{:#?}", error),
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| format!("invalid duration `{s}`, expected a positive number of seconds"))
+}
+
+/// Prints a message for people, sending it to stderr instead when stdout is machine-readable.
+fn notice(format: Format, message: String) {
+    match format {
+        Format::Text => println!("{message}"),
+        Format::Json | Format::Tsv => eprintln!("{message}"),
     }
 }
+
+fn get_challenge(day: u8) -> Result<&'static ChallengeInfo> {
+    challenge::get(day).ok_or(anyhow!("No challenge exists for day {day}."))
+}
+
+fn read_input(day: u8, input: Option<PathBuf>) -> Result<String> {
+    let path = input.unwrap_or_else(|| runner::default_input_path(day));
+
+    if path.as_os_str() == "-" {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("Couldn't read input from stdin")?;
+        Ok(buf)
+    } else {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Couldn't read input file {}", path.display()))
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    logging::init(cli.verbose, cli.quiet);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            answers,
+            timeout,
+        } => {
+            let info = get_challenge(day)?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![Part::One, Part::Two],
+            };
+
+            if let Some(pattern) = input.as_deref().filter(|input| runner::is_batch(input)) {
+                if answers.is_some() {
+                    bail!(
+                        "--answers only applies to a single input; each of several inputs is checked against the .toml next to it."
+                    );
+                }
+
+                let reports =
+                    runner::run_batch(info, &runner::batch_inputs(pattern)?, &parts, timeout);
+                output::print_batch(cli.format, &reports);
+
+                let failures = reports.iter().filter(|run| run.report.is_failure()).count();
+                if failures > 0 {
+                    bail!("{failures} input(s) failed.");
+                }
+                return Ok(());
+            }
+
+            let expected = match (answers, &input) {
+                (Some(path), _) => ExpectedAnswers::load(&path)?,
+                (None, None) => ExpectedAnswers::load(&answers::default_answers_path(day))?,
+                (None, Some(_)) => ExpectedAnswers::default(),
+            };
+            let input = read_input(day, input)?;
+
+            let report = runner::run(info, &input, &parts, &expected, timeout);
+            output::print_run(cli.format, &report);
+
+            match report.status {
+                Status::Error(error) => return Err(error),
+                Status::Panicked(_) | Status::TimedOut(_) => bail!("Day {day} {}", report.status),
+                _ if report.parts.iter().any(|part| part.answer.is_err()) => {
+                    bail!("Not every part of day {day} produced an answer.")
+                }
+                _ if report.is_failure() => bail!("Answers didn't match the expected answers."),
+                _ => {}
+            }
+        }
+        Command::All { timeout } => {
+            let reports = runner::run_all(timeout);
+            output::print_all(cli.format, &reports);
+
+            let failures = reports.iter().filter(|report| report.is_failure()).count();
+            if failures > 0 {
+                bail!("{failures} challenge(s) failed.");
+            }
+        }
+        Command::Bench {
+            target,
+            iterations,
+            json,
+        } => {
+            let reports = match target {
+                Target::Day(day) => {
+                    let info = get_challenge(day)?;
+                    vec![bench::bench(info, &read_input(day, None)?, iterations)?]
+                }
+                Target::All => CHALLENGES
+                    .iter()
+                    .filter_map(|info| {
+                        let input =
+                            std::fs::read_to_string(runner::default_input_path(info.day)).ok()?;
+                        bench::bench(info, &input, iterations)
+                            .inspect_err(|error| error!("Day {}: {error:#}", info.day))
+                            .ok()
+                    })
+                    .collect(),
+            };
+
+            output::print_bench(cli.format, &reports);
+
+            if let Some(path) = json {
+                std::fs::write(&path, serde_json::to_string_pretty(&reports)?)
+                    .with_context(|| format!("Couldn't write {}", path.display()))?;
+            }
+        }
+        Command::List => output::print_list(cli.format, CHALLENGES),
+        Command::Fetch { day } => {
+            let config = client::Config::load(&client::default_config_path())?;
+            let path = runner::default_input_path(day);
+
+            match client::fetch_input(&config, day, &path)? {
+                client::Fetched::Downloaded => {
+                    notice(cli.format, format!("Saved input to {}", path.display()))
+                }
+                client::Fetched::AlreadyCached => notice(
+                    cli.format,
+                    format!("Input is already at {}", path.display()),
+                ),
+            }
+        }
+        Command::New { day, title } => {
+            let title = title.unwrap_or_else(|| format!("Day {day}"));
+
+            for path in scaffold::new_day(Path::new("."), day, &title)? {
+                notice(cli.format, format!("Created {}", path.display()));
+            }
+        }
+        Command::Submit { day, part } => {
+            let info = get_challenge(day)?;
+            let input = read_input(day, None)?;
+
+            let mut report = runner::run(info, &input, &[part], &ExpectedAnswers::default(), None);
+            match report.status {
+                Status::Error(error) => return Err(error),
+                Status::Panicked(_) | Status::TimedOut(_) => bail!("Day {day} {}", report.status),
+                _ => {}
+            }
+            let answer = &report.parts.remove(0).answer?;
+            if *answer == Answer::Unsolved {
+                bail!("Part {part} of day {day} isn't solved yet.");
+            }
+
+            let history_path = history::default_history_path();
+            let mut history = history::History::load(&history_path)?;
+            history.check(day, part, answer, history::now())?;
+
+            let config = client::Config::load(&client::default_config_path())?;
+            let page = client::Client::new(&config)?.post_answer(day, part, answer)?;
+
+            // The answer has been sent, so it's recorded even if the response makes no sense.
+            let outcome = client::Outcome::parse(&page);
+            let recorded = outcome
+                .as_ref()
+                .map_or(client::Outcome::Unknown, Clone::clone);
+            history.record(day, part, answer, recorded, history::now());
+            history.save(&history_path)?;
+
+            let outcome = outcome?;
+            notice(cli.format, format!("Part {part}: {answer} ({outcome})"));
+
+            if outcome != client::Outcome::Correct {
+                bail!("The answer wasn't accepted.");
+            }
+        }
+        Command::Watch {
+            day,
+            input,
+            fixtures,
+            interval,
+            timeout,
+        } => {
+            let info = get_challenge(day)?;
+            let input = input.unwrap_or_else(|| runner::default_input_path(day));
+            let fixtures =
+                fixtures.map(|dir| dir.unwrap_or_else(|| runner::default_fixture_dir(day)));
+
+            watch::Session::new(info, input, fixtures, timeout).watch(interval);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    bench::BenchReport,
+    challenge::{Answer, ChallengeInfo, Part},
+    runner::{InputReport, PartReport, Report, Status},
+    utils::format_table,
+};
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    /// Human-readable lines and tables.
+    #[default]
+    Text,
+    /// A single JSON document.
+    Json,
+    /// Tab-separated values with a header row.
+    Tsv,
+}
+
+#[derive(Serialize)]
+struct PartJson<'a> {
+    part: u8,
+    answer: Option<&'a Answer>,
+    verdict: &'static str,
+    expected: Option<&'a Answer>,
+    error: Option<String>,
+    elapsed_ns: u128,
+}
+
+#[derive(Serialize)]
+struct ReportJson<'a> {
+    day: u8,
+    title: &'a str,
+    status: &'static str,
+    error: Option<String>,
+    elapsed_ns: u128,
+    parts: Vec<PartJson<'a>>,
+}
+
+impl<'a> From<&'a Report> for ReportJson<'a> {
+    fn from(report: &'a Report) -> Self {
+        Self {
+            day: report.day,
+            title: report.title,
+            status: report.status.kind(),
+            error: report.status.message(),
+            elapsed_ns: report.elapsed.as_nanos(),
+            parts: report
+                .parts
+                .iter()
+                .map(|part| PartJson {
+                    part: part.part.number(),
+                    answer: part.answer.as_ref().ok(),
+                    verdict: part.verdict.short(),
+                    expected: part.verdict.expected(),
+                    error: part.answer.as_ref().err().map(|error| format!("{error:#}")),
+                    elapsed_ns: part.elapsed.as_nanos(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Keeps a free-text field on one TSV line.
+fn tsv_field(field: impl ToString) -> String {
+    field.to_string().replace(['\t', '\n', '\r'], " ")
+}
+
+const TSV_HEADER: &str = "day\tpart\tanswer\tverdict\telapsed_ns\tstatus\terror";
+
+/// A report's TSV lines, one per part that ran, or a single line if none did. A part that
+/// errored has its own status and error.
+fn tsv_lines(report: &Report) -> Vec<String> {
+    let error = report.status.message().map(tsv_field).unwrap_or_default();
+
+    if report.parts.is_empty() {
+        return vec![format!(
+            "{}\t\t\t\t{}\t{}\t{error}",
+            report.day,
+            report.elapsed.as_nanos(),
+            report.status.kind()
+        )];
+    }
+
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            let (answer, status, error) = match &part.answer {
+                Ok(answer) => (tsv_field(answer), report.status.kind(), error.clone()),
+                Err(part_error) => (String::new(), "error", tsv_field(format!("{part_error:#}"))),
+            };
+            format!(
+                "{}\t{}\t{answer}\t{}\t{}\t{status}\t{error}",
+                report.day,
+                part.part,
+                part.verdict.short(),
+                part.elapsed.as_nanos(),
+            )
+        })
+        .collect()
+}
+
+fn tsv(reports: &[Report]) -> String {
+    let mut out = format!("{TSV_HEADER}\n");
+
+    for line in reports.iter().flat_map(tsv_lines) {
+        out += &format!("{line}\n");
+    }
+
+    out
+}
+
+fn json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).expect("Reports should always serialize") + "\n"
+}
+
+/// A part's answer and verdict, or the error it returned.
+fn describe_part(part: &PartReport) -> String {
+    match &part.answer {
+        Ok(answer) => format!("Part {}: {answer} ({})", part.part, part.verdict),
+        Err(error) => format!("Part {}: error: {error:#}", part.part),
+    }
+}
+
+/// Prints the result of running a single challenge.
+pub fn print_run(format: Format, report: &Report) {
+    match format {
+        Format::Text => {
+            if !report.parts.is_empty() {
+                let parts = report.parts.iter().map(describe_part).collect::<Vec<_>>();
+                println!("{}", parts.join(", "));
+            }
+        }
+        Format::Json => print!("{}", json(&ReportJson::from(report))),
+        Format::Tsv => print!("{}", tsv(std::slice::from_ref(report))),
+    }
+}
+
+/// A part's check for a summary table: its verdict, or `error` if it didn't produce an answer.
+fn check_cell(part: &PartReport) -> &'static str {
+    match part.answer {
+        Ok(_) => part.verdict.short(),
+        Err(_) => "error",
+    }
+}
+
+/// A report's status for a summary table, after the errors of any parts that failed.
+fn status_cell(report: &Report) -> String {
+    let mut statuses = report
+        .parts
+        .iter()
+        .filter_map(|part| {
+            let error = part.answer.as_ref().err()?;
+            Some(format!("part {}: {error:#}", part.part))
+        })
+        .collect::<Vec<_>>();
+
+    if statuses.is_empty() || !matches!(report.status, Status::Ok) {
+        statuses.push(report.status.to_string());
+    }
+    statuses.join("; ")
+}
+
+/// A summary table row for a report: its label, answers, checks, time and status.
+fn summary_row(label: String, report: &Report) -> [String; 6] {
+    let [part_1, part_2] = [Part::One, Part::Two].map(|part| report.part(part));
+    let answer_cell = |part: Option<&PartReport>| match part.map(|part| &part.answer) {
+        Some(Ok(answer)) => answer.to_string(),
+        _ => "-".to_string(),
+    };
+
+    [
+        label,
+        answer_cell(part_1),
+        answer_cell(part_2),
+        match (part_1, part_2) {
+            (Some(part_1), Some(part_2)) => {
+                format!("{}/{}", check_cell(part_1), check_cell(part_2))
+            }
+            (Some(part), None) | (None, Some(part)) => check_cell(part).to_string(),
+            (None, None) => "-".to_string(),
+        },
+        match report.status {
+            Status::MissingInput => "-".to_string(),
+            _ => format!("{:.2?}", report.elapsed),
+        },
+        status_cell(report),
+    ]
+}
+
+/// Prints the results of running several challenges.
+pub fn print_all(format: Format, reports: &[Report]) {
+    match format {
+        Format::Text => {
+            let rows = reports
+                .iter()
+                .map(|report| summary_row(report.day.to_string(), report))
+                .collect::<Vec<_>>();
+
+            let header = ["Day", "Part 1", "Part 2", "Check", "Time", "Status"];
+            print!("{}", format_table(header, &rows));
+        }
+        Format::Json => print!(
+            "{}",
+            json(&reports.iter().map(ReportJson::from).collect::<Vec<_>>())
+        ),
+        Format::Tsv => print!("{}", tsv(reports)),
+    }
+}
+
+#[derive(Serialize)]
+struct InputReportJson<'a> {
+    input: String,
+    #[serde(flatten)]
+    report: ReportJson<'a>,
+}
+
+/// Prints the results of running one challenge against several inputs.
+pub fn print_batch(format: Format, reports: &[InputReport]) {
+    match format {
+        Format::Text => {
+            let rows = reports
+                .iter()
+                .map(|run| summary_row(run.input.display().to_string(), &run.report))
+                .collect::<Vec<_>>();
+
+            let header = ["Input", "Part 1", "Part 2", "Check", "Time", "Status"];
+            print!("{}", format_table(header, &rows));
+        }
+        Format::Json => print!(
+            "{}",
+            json(
+                &reports
+                    .iter()
+                    .map(|run| InputReportJson {
+                        input: run.input.display().to_string(),
+                        report: ReportJson::from(&run.report),
+                    })
+                    .collect::<Vec<_>>()
+            )
+        ),
+        Format::Tsv => {
+            println!("input\t{TSV_HEADER}");
+            for run in reports {
+                for line in tsv_lines(&run.report) {
+                    println!("{}\t{line}", tsv_field(run.input.display()));
+                }
+            }
+        }
+    }
+}
+
+pub fn print_bench(format: Format, reports: &[BenchReport]) {
+    match format {
+        Format::Text => {
+            let rows = reports
+                .iter()
+                .flat_map(|report| {
+                    report.stages().map(|(stage, stats)| {
+                        [
+                            report.day.to_string(),
+                            stage.to_string(),
+                            format!("{:.2?}", stats.min),
+                            format!("{:.2?}", stats.median),
+                            format!("{:.2?}", stats.mean),
+                        ]
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let header = ["Day", "Stage", "Min", "Median", "Mean"];
+            print!("{}", format_table(header, &rows));
+        }
+        Format::Json => print!("{}", json(&reports)),
+        Format::Tsv => {
+            println!("day\tstage\titerations\tmin_ns\tmedian_ns\tmean_ns");
+            for report in reports {
+                for (stage, stats) in report.stages() {
+                    println!(
+                        "{}\t{stage}\t{}\t{}\t{}\t{}",
+                        report.day,
+                        report.iterations,
+                        stats.min.as_nanos(),
+                        stats.median.as_nanos(),
+                        stats.mean.as_nanos()
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub fn print_list(format: Format, challenges: &[ChallengeInfo]) {
+    #[derive(Serialize)]
+    struct Entry {
+        day: u8,
+        title: &'static str,
+    }
+
+    match format {
+        Format::Text => challenges
+            .iter()
+            .for_each(|info| println!("{:>2}  {}", info.day, info.title)),
+        Format::Json => print!(
+            "{}",
+            json(
+                &challenges
+                    .iter()
+                    .map(|info| Entry {
+                        day: info.day,
+                        title: info.title,
+                    })
+                    .collect::<Vec<_>>()
+            )
+        ),
+        Format::Tsv => {
+            println!("day\ttitle");
+            challenges
+                .iter()
+                .for_each(|info| println!("{}\t{}", info.day, info.title));
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, bail};
+use log::{info, warn};
+
+use crate::{
+    answers::{ExpectedAnswers, Verdict, default_answers_path, sibling_answers_path},
+    challenge::{Answer, CHALLENGES, ChallengeInfo, Part},
+    logging::DayScope,
+};
+
+pub fn default_input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("input/Challenge{day}.txt"))
+}
+
+pub fn default_fixture_dir(day: u8) -> PathBuf {
+    PathBuf::from(format!("fixtures/challenge_{day}"))
+}
+
+/// Lists the `.txt` inputs in a directory, sorted by name.
+pub fn inputs_in(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut inputs = std::fs::read_dir(dir)
+        .with_context(|| format!("Couldn't read {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.extension().is_some_and(|ext| ext == "txt")
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+pub enum Status {
+    Ok,
+    Error(anyhow::Error),
+    Panicked(String),
+    TimedOut(Duration),
+    MissingInput,
+}
+
+impl Status {
+    /// A stable name for the status, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error(_) => "error",
+            Status::Panicked(_) => "panicked",
+            Status::TimedOut(_) => "timed_out",
+            Status::MissingInput => "missing_input",
+        }
+    }
+
+    /// The error or panic message, if there is one.
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Status::Error(error) => Some(format!("{error:#}")),
+            Status::Panicked(message) => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Error(error) => write!(f, "error: {error:#}"),
+            Status::Panicked(message) => write!(f, "panicked: {message}"),
+            Status::TimedOut(timeout) => write!(f, "timed out after {}s", timeout.as_secs_f64()),
+            Status::MissingInput => write!(f, "missing input"),
+        }
+    }
+}
+
+pub struct PartReport {
+    pub part: Part,
+    /// The part's answer, or the error it returned instead.
+    pub answer: anyhow::Result<Answer>,
+    pub verdict: Verdict,
+    pub elapsed: Duration,
+}
+
+impl PartReport {
+    /// Whether the part returned an error or a wrong answer.
+    pub fn is_failure(&self) -> bool {
+        self.answer.is_err() || self.verdict.is_fail()
+    }
+}
+
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub elapsed: Duration,
+    pub status: Status,
+    /// The parts that ran, in the order they were run.
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    fn new(info: &ChallengeInfo, status: Status) -> Self {
+        Self {
+            day: info.day,
+            title: info.title,
+            elapsed: Duration::ZERO,
+            status,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Whether the challenge as a whole errored, panicked or ran out of time.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.status,
+            Status::Error(_) | Status::Panicked(_) | Status::TimedOut(_)
+        )
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartReport> {
+        self.parts.iter().find(|report| report.part == part)
+    }
+
+    /// Whether the challenge failed to run, or any part errored or gave a wrong answer.
+    pub fn is_failure(&self) -> bool {
+        self.is_error() || self.parts.iter().any(PartReport::is_failure)
+    }
+}
+
+/// Something the challenge thread has to report back to [`run`].
+enum Event {
+    Answer(Part, anyhow::Result<Answer>, Duration),
+    Done,
+    Failed(anyhow::Error),
+    Panicked(String),
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+thread_local! {
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Whether the run on this thread has timed out. Long-running challenges can check this to give
+/// up early, since there's no way to stop their thread from outside.
+pub fn cancelled() -> bool {
+    CANCEL.with_borrow(|cancel| {
+        cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    })
+}
+
+/// Runs the given parts of a challenge, timing each one and checking it against the expected
+/// answers. A part that returns an error doesn't stop the others, since they only share the
+/// parsed input; an error parsing it is reported as [`Status::Error`].
+///
+/// The challenge runs on its own thread, so a panic is reported as [`Status::Panicked`] rather
+/// than taking the process down. If it's still running after `timeout`, it's reported as
+/// [`Status::TimedOut`] and asked to stop: the thread skips any parts it hasn't started, but a
+/// part that's already running carries on using CPU until it finishes or checks [`cancelled`].
+pub fn run(
+    info: &ChallengeInfo,
+    input: &str,
+    parts: &[Part],
+    expected: &ExpectedAnswers,
+    timeout: Option<Duration>,
+) -> Report {
+    info!("Running day {}: {}", info.day, info.title);
+
+    let mut report = Report::new(info, Status::Ok);
+    let start = Instant::now();
+
+    let (sender, receiver) = mpsc::channel();
+    let (day, constructor) = (info.day, info.constructor);
+    let (input, parts) = (input.to_string(), parts.to_vec());
+    let cancel = Arc::new(AtomicBool::new(false));
+    let worker_cancel = Arc::clone(&cancel);
+
+    let spawned = thread::Builder::new()
+        .name(format!("day {day}"))
+        .spawn(move || {
+            let _scope = DayScope::enter(day);
+            CANCEL.set(Some(worker_cancel));
+
+            // Sends only fail once `run` has given up waiting, so they're safe to ignore.
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| -> anyhow::Result<()> {
+                let challenge = constructor();
+                let prepared = challenge.prepare(&input)?;
+
+                for part in parts {
+                    if cancelled() {
+                        break;
+                    }
+                    let start = Instant::now();
+                    let answer = prepared.run_part(part);
+                    let _ = sender.send(Event::Answer(part, answer, start.elapsed()));
+                }
+                Ok(())
+            }));
+
+            let _ = sender.send(match outcome {
+                Ok(Ok(())) => Event::Done,
+                Ok(Err(error)) => Event::Failed(error),
+                Err(payload) => Event::Panicked(panic_message(&*payload)),
+            });
+        });
+
+    if let Err(error) = spawned {
+        report.status =
+            Status::Error(anyhow::Error::new(error).context("Couldn't start the challenge"));
+        return report;
+    }
+
+    report.status = loop {
+        let event = match timeout {
+            Some(timeout) => match receiver.recv_timeout(timeout.saturating_sub(start.elapsed())) {
+                Err(RecvTimeoutError::Timeout) => {
+                    cancel.store(true, Ordering::Relaxed);
+                    warn!(
+                        "Day {day} timed out; its thread keeps running until it next checks for \
+                         cancellation"
+                    );
+                    break Status::TimedOut(timeout);
+                }
+                event => event.ok(),
+            },
+            None => receiver.recv().ok(),
+        };
+
+        match event {
+            Some(Event::Answer(part, answer, elapsed)) => report.parts.push(PartReport {
+                part,
+                verdict: match &answer {
+                    Ok(answer) => expected.check(part, answer),
+                    Err(_) => Verdict::Unknown,
+                },
+                answer,
+                elapsed,
+            }),
+            Some(Event::Done) => break Status::Ok,
+            Some(Event::Failed(error)) => break Status::Error(error),
+            Some(Event::Panicked(message)) => break Status::Panicked(message),
+            None => {
+                break Status::Panicked("the challenge thread stopped without reporting".into());
+            }
+        }
+    };
+    report.elapsed = start.elapsed();
+
+    report
+}
+
+/// Whether an input path names several inputs: a directory, or a glob like `inputs/*.txt`.
+pub fn is_batch(input: &Path) -> bool {
+    input.is_dir()
+        || input
+            .to_str()
+            .is_some_and(|input| input.contains(['*', '?', '[']))
+}
+
+/// Expands a directory or glob into the input files it names, sorted by path.
+pub fn batch_inputs(input: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let inputs = if input.is_dir() {
+        inputs_in(input)?
+    } else {
+        let pattern = input
+            .to_str()
+            .with_context(|| format!("{} isn't valid UTF-8", input.display()))?;
+        let mut inputs = glob::glob(pattern)
+            .with_context(|| format!("Invalid glob `{pattern}`"))?
+            // Answers files sit next to their inputs, so skip them if the glob catches them too.
+            .filter(|path| {
+                path.as_ref().map_or(true, |path| {
+                    path.is_file() && path.extension().is_none_or(|ext| ext != "toml")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        inputs.sort();
+        inputs
+    };
+
+    if inputs.is_empty() {
+        bail!("No inputs found at {}", input.display());
+    }
+    Ok(inputs)
+}
+
+/// The result of running a challenge against one of several inputs.
+pub struct InputReport {
+    pub input: PathBuf,
+    pub report: Report,
+}
+
+/// Runs the given parts of a challenge against each input, checking each against the answers
+/// file next to it, if there is one.
+pub fn run_batch(
+    info: &ChallengeInfo,
+    inputs: &[PathBuf],
+    parts: &[Part],
+    timeout: Option<Duration>,
+) -> Vec<InputReport> {
+    inputs
+        .iter()
+        .map(|input| {
+            let loaded = std::fs::read_to_string(input)
+                .with_context(|| format!("Couldn't read input file {}", input.display()))
+                .and_then(|contents| {
+                    Ok((
+                        contents,
+                        ExpectedAnswers::load(&sibling_answers_path(input))?,
+                    ))
+                });
+
+            let report = match loaded {
+                Ok((contents, expected)) => run(info, &contents, parts, &expected, timeout),
+                Err(error) => Report::new(info, Status::Error(error)),
+            };
+
+            InputReport {
+                input: input.clone(),
+                report,
+            }
+        })
+        .collect()
+}
+
+/// Runs every challenge against its default input and checks it against its default answers,
+/// carrying on past any that fail.
+pub fn run_all(timeout: Option<Duration>) -> Vec<Report> {
+    CHALLENGES
+        .iter()
+        .map(|info| {
+            let Ok(input) = std::fs::read_to_string(default_input_path(info.day)) else {
+                return Report::new(info, Status::MissingInput);
+            };
+
+            match ExpectedAnswers::load(&default_answers_path(info.day)) {
+                Ok(expected) => run(info, &input, &[Part::One, Part::Two], &expected, timeout),
+                Err(error) => Report::new(info, Status::Error(error)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::AOCChallenge;
+
+    static STOPPED_SPINNING: AtomicBool = AtomicBool::new(false);
+
+    #[derive(Default)]
+    struct Misbehaving;
+
+    impl AOCChallenge for Misbehaving {
+        type Input<'a> = &'a str;
+
+        fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {
+            Ok(input)
+        }
+
+        fn part_1(&self, input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+            match *input {
+                "fail" => anyhow::bail!("Needs a second example"),
+                _ => Ok(1.into()),
+            }
+        }
+
+        fn part_2(&self, input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+            match *input {
+                "panic" => panic!("Can't do diagonals!"),
+                "spin" => {
+                    while !cancelled() {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                    STOPPED_SPINNING.store(true, Ordering::Relaxed);
+                }
+                _ => {}
+            }
+            Ok(2.into())
+        }
+    }
+
+    const MISBEHAVING: ChallengeInfo = ChallengeInfo::new::<Misbehaving>(99, "Misbehaving");
+
+    fn run_both(input: &str, timeout: Option<Duration>) -> Report {
+        let parts = [Part::One, Part::Two];
+        run(
+            &MISBEHAVING,
+            input,
+            &parts,
+            &ExpectedAnswers::default(),
+            timeout,
+        )
+    }
+
+    #[test]
+    fn batch_inputs_skip_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["b.txt", "a.txt", "a.toml"] {
+            std::fs::write(dir.path().join(file), "").unwrap();
+        }
+        let expected = [dir.path().join("a.txt"), dir.path().join("b.txt")];
+
+        assert!(is_batch(dir.path()));
+        assert_eq!(batch_inputs(dir.path()).unwrap(), expected);
+
+        let pattern = dir.path().join("*");
+        assert!(is_batch(&pattern));
+        assert_eq!(batch_inputs(&pattern).unwrap(), expected);
+
+        assert!(!is_batch(&dir.path().join("a.txt")));
+        assert!(batch_inputs(&dir.path().join("*.csv")).is_err());
+    }
+
+    #[test]
+    fn runs_to_completion() {
+        let report = run_both("fine", Some(Duration::from_secs(10)));
+
+        assert!(matches!(report.status, Status::Ok));
+        assert_eq!(report.parts.len(), 2);
+    }
+
+    #[test]
+    fn panics_are_reported() {
+        let report = run_both("panic", None);
+
+        assert_eq!(report.status.to_string(), "panicked: Can't do diagonals!");
+        assert!(report.is_failure());
+        assert_eq!(
+            report.part(Part::One).unwrap().answer.as_ref().unwrap(),
+            &1.into()
+        );
+    }
+
+    #[test]
+    fn failing_parts_dont_stop_the_others() {
+        let report = run_both("fail", None);
+
+        assert!(matches!(report.status, Status::Ok));
+        assert!(report.is_failure());
+        let part_1 = report.part(Part::One).unwrap();
+        assert_eq!(
+            part_1.answer.as_ref().unwrap_err().to_string(),
+            "Needs a second example"
+        );
+        assert_eq!(
+            report.part(Part::Two).unwrap().answer.as_ref().unwrap(),
+            &2.into()
+        );
+    }
+
+    #[test]
+    fn slow_challenges_time_out() {
+        let report = run_both("spin", Some(Duration::from_millis(50)));
+
+        assert_eq!(report.status.to_string(), "timed out after 0.05s");
+        assert!(report.is_failure());
+        assert_eq!(report.parts.len(), 1);
+
+        // The timeout asks the challenge to stop, so its thread doesn't spin forever.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !STOPPED_SPINNING.load(Ordering::Relaxed) {
+            assert!(
+                Instant::now() < deadline,
+                "the challenge never saw the cancellation"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+const REGISTRY: &str = "src/challenge/mod.rs";
+
+fn module_source(day: u8, title: &str) -> String {
+    format!(
+        r#"use super::{{AOCChallenge, ChallengeInfo}};
+
+#[derive(Debug, Default)]
+pub struct Challenge;
+
+pub const INFO: ChallengeInfo = ChallengeInfo::new::<Challenge>({day}, {title:?});
+
+impl AOCChallenge for Challenge {{
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Input<'a>> {{
+        Ok(input.lines().collect())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use crate::challenge::test_utils::check_example;
+
+    #[test]
+    #[ignore = "the example and its answers haven't been filled in yet"]
+    fn example() {{
+        check_example(Challenge, {day}, "example");
+    }}
+}}
+"#
+    )
+}
+
+const ANSWERS_STUB: &str = "# part_1 = \n# part_2 = \n";
+
+/// Adds a module to the `challenges!` list in the source of `challenge/mod.rs`, keeping the list
+/// in day order.
+fn register(registry: &str, day: u8) -> Result<String> {
+    let start = registry
+        .find("challenges!(\n")
+        .ok_or(anyhow!("Couldn't find the `challenges!` list"))?
+        + "challenges!(\n".len();
+    let end = start
+        + registry[start..]
+            .find(");")
+            .ok_or(anyhow!("Couldn't find the end of the `challenges!` list"))?;
+
+    let module = format!("challenge_{day}");
+    let mut modules = registry[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .collect::<Vec<_>>();
+
+    if modules.contains(&module.as_str()) {
+        bail!("Day {day} is already registered");
+    }
+    modules.push(&module);
+    modules.sort_by_key(|module| {
+        module
+            .strip_prefix("challenge_")
+            .and_then(|day| day.parse::<u8>().ok())
+    });
+
+    let list = modules
+        .iter()
+        .map(|module| format!("    {module},\n"))
+        .collect::<String>();
+
+    Ok(format!("{}{list}{}", &registry[..start], &registry[end..]))
+}
+
+/// Writes `contents` to a new file, refusing to replace an existing one.
+fn create(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Couldn't create directory {}", dir.display()))?;
+    }
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, contents.as_bytes()))
+        .with_context(|| format!("Couldn't create {}", path.display()))
+}
+
+/// Creates the module, example fixture and answer stubs for a new day under the repository at
+/// `root`, and registers the module. Returns the files it created.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+    let registry_path = root.join(REGISTRY);
+    let registry = std::fs::read_to_string(&registry_path).with_context(|| {
+        format!(
+            "Couldn't read {}; is this the root of the repository?",
+            registry_path.display()
+        )
+    })?;
+
+    let module_path = root.join(format!("src/challenge/challenge_{day}.rs"));
+    if module_path.exists() {
+        bail!("{} already exists", module_path.display());
+    }
+    let registry = register(&registry, day)?;
+
+    let fixtures = root.join(format!("fixtures/challenge_{day}"));
+    let files = [
+        (module_path, module_source(day, title)),
+        (fixtures.join("example.txt"), String::new()),
+        (fixtures.join("example.toml"), ANSWERS_STUB.to_string()),
+        (
+            root.join(format!("answers/{day}.toml")),
+            ANSWERS_STUB.to_string(),
+        ),
+    ];
+
+    let mut created = Vec::new();
+    let written = files
+        .into_iter()
+        .filter(|(path, _)| !path.exists())
+        .try_for_each(|(path, contents)| {
+            create(&path, &contents)?;
+            created.push(path);
+            Ok(())
+        })
+        .and_then(|()| {
+            std::fs::write(&registry_path, registry)
+                .with_context(|| format!("Couldn't write {}", registry_path.display()))
+        });
+
+    // A module that isn't registered, or a registration without its module, would leave the
+    // crate half-scaffolded, so undo everything if any step failed.
+    if let Err(error) = written {
+        for path in &created {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(error);
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY_SOURCE: &str = "mod answer;\n\nchallenges!(\n    challenge_1,\n    challenge_2,\n    challenge_10,\n);\n\npub fn get() {}\n";
+
+    #[test]
+    fn register_keeps_day_order() {
+        assert_eq!(
+            register(REGISTRY_SOURCE, 3).unwrap(),
+            "mod answer;\n\nchallenges!(\n    challenge_1,\n    challenge_2,\n    challenge_3,\n    challenge_10,\n);\n\npub fn get() {}\n"
+        );
+        assert!(register(REGISTRY_SOURCE, 2).is_err());
+    }
+
+    #[test]
+    fn new_day_creates_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/challenge")).unwrap();
+        std::fs::write(root.join(REGISTRY), REGISTRY_SOURCE).unwrap();
+
+        let created = new_day(root, 3, "Some \"Title\"").unwrap();
+        assert_eq!(created.len(), 4);
+
+        let module = std::fs::read_to_string(root.join("src/challenge/challenge_3.rs")).unwrap();
+        assert!(module.contains(r#"ChallengeInfo::new::<Challenge>(3, "Some \"Title\"")"#));
+        assert!(
+            std::fs::read_to_string(root.join(REGISTRY))
+                .unwrap()
+                .contains("    challenge_3,\n")
+        );
+        assert!(root.join("fixtures/challenge_3/example.txt").exists());
+        assert!(root.join("answers/3.toml").exists());
+
+        assert!(new_day(root, 3, "Again").is_err());
+    }
+
+    #[test]
+    fn failed_new_day_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/challenge")).unwrap();
+        std::fs::write(root.join(REGISTRY), REGISTRY_SOURCE).unwrap();
+        // A file where the fixtures directory should go stops the scaffold part way through.
+        std::fs::create_dir(root.join("fixtures")).unwrap();
+        std::fs::write(root.join("fixtures/challenge_3"), "").unwrap();
+
+        assert!(new_day(root, 3, "Title").is_err());
+        assert!(!root.join("src/challenge/challenge_3.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(root.join(REGISTRY)).unwrap(),
+            REGISTRY_SOURCE
+        );
+    }
+}
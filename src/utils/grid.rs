@@ -0,0 +1,255 @@
+use std::{
+    fmt::Display,
+    ops::{Deref, DerefMut},
+};
+
+use ndarray::Array2;
+
+use super::ParseError;
+
+/// Offsets to the four orthogonal neighbours of a tile.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to all eight neighbours of a tile, including diagonals.
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A tile that can be drawn as a single character.
+pub trait GridTile {
+    fn to_char(&self) -> char;
+}
+
+impl GridTile for bool {
+    fn to_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+impl GridTile for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// A rectangular map of tiles indexed by `(row, col)`. Derefs to the underlying [`Array2`] for
+/// slicing and the rest of `ndarray`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T>(Array2<T>);
+
+impl<T> Grid<T> {
+    /// Parses one tile per character of each line of `input`, using `tile` to map characters to
+    /// tiles. `expected` describes the characters `tile` accepts.
+    pub fn parse(
+        input: &str,
+        tile: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        Self::from_rows(input, input.lines(), tile, expected)
+    }
+
+    /// Like [`Grid::parse`], but for a grid made of some of the lines of `input`.
+    pub fn from_rows<'a>(
+        input: &str,
+        rows: impl IntoIterator<Item = &'a str>,
+        tile: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut num_rows = 0;
+        let mut num_cols = None;
+        let mut tiles = Vec::new();
+
+        for row in rows {
+            let len = row.chars().count();
+            match num_cols {
+                Some(cols) if cols != len => {
+                    return Err(ParseError::at(input, row, format!("a row of {cols} tiles")));
+                }
+                _ => num_cols = Some(len),
+            }
+
+            for (i, ch) in row.char_indices() {
+                let tile = tile(ch)
+                    .ok_or_else(|| ParseError::at(input, &row[i..i + ch.len_utf8()], expected))?;
+                tiles.push(tile);
+            }
+            num_rows += 1;
+        }
+
+        let num_cols =
+            num_cols.ok_or_else(|| ParseError::at_end(input, format!("a grid of {expected}")))?;
+        let tiles = Array2::from_shape_vec((num_rows, num_cols), tiles)
+            .expect("Every row should have the same number of tiles");
+
+        Ok(Self(tiles))
+    }
+
+    pub fn into_inner(self) -> Array2<T> {
+        self.0
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.0.get(pos)
+    }
+
+    /// The position `delta` away from `pos`, if it's inside the grid.
+    pub fn offset(&self, pos: (usize, usize), delta: (isize, isize)) -> Option<(usize, usize)> {
+        let row = pos
+            .0
+            .checked_add_signed(delta.0)
+            .filter(|row| *row < self.nrows())?;
+        let col = pos
+            .1
+            .checked_add_signed(delta.1)
+            .filter(|col| *col < self.ncols())?;
+
+        Some((row, col))
+    }
+
+    /// The orthogonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self {
+        Self(Array2::from_elem((rows, cols), fill))
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// Sets every tile that's orthogonally connected to `start` through tiles equal to it to
+    /// `fill`.
+    pub fn flood_fill(&mut self, start: (usize, usize), fill: T) {
+        let Some(target) = self.get(start).cloned() else {
+            return;
+        };
+        if target == fill {
+            return;
+        }
+
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            if self[pos] != target {
+                continue;
+            }
+            self[pos] = fill.clone();
+
+            stack.extend(self.neighbours4(pos).filter(|next| self[*next] == target));
+        }
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(tiles: Array2<T>) -> Self {
+        Self(tiles)
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = Array2<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: GridTile> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            row.iter()
+                .try_for_each(|tile| write!(f, "{}", tile.to_char()))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_bools(input: &str) -> Result<Grid<bool>, ParseError> {
+        Grid::parse(
+            input,
+            |ch| match ch {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            },
+            "`#` or `.`",
+        )
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = parse_bools("#..\n.#.\n").unwrap();
+
+        assert_eq!(grid.dim(), (2, 3));
+        assert_eq!(grid.get((1, 1)), Some(&true));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "#..\n.#.");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_bools("#..\n.x.\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (2, 2, "x")
+        );
+
+        let error = parse_bools("#..\n.#\n").unwrap_err();
+        assert_eq!(error.expected, "a row of 3 tiles");
+
+        let error = parse_bools("").unwrap_err();
+        assert_eq!(error.expected, "a grid of `#` or `.`");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, ());
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.offset((2, 2), (1, 0)), None);
+    }
+
+    #[test]
+    fn flood_fill() {
+        let mut grid = parse_bools("..#.\n.#..\n#...\n").unwrap();
+        grid.flood_fill((0, 0), true);
+
+        assert_eq!(grid.to_string(), "###.\n##..\n#...");
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::{Result, anyhow, bail};
+use ndarray::Array2;
+
+mod grid;
+mod ordinal_map;
+mod parse;
+mod range_set;
+
+pub use grid::{Grid, GridTile};
+pub use ordinal_map::OrdinalMap;
+pub use parse::{ParseError, char_slices, parse_at};
+pub use range_set::RangeSet;
+
+/// How [`ragged_to_arr`] handles rows of different lengths.
+#[derive(Debug, Clone, Copy)]
+pub enum Ragged<T> {
+    /// Fail, naming the first row that isn't as long as the first row.
+    Reject,
+    /// Pad short rows on the right with the given value, so they line up on the left.
+    AlignLeft(T),
+    /// Pad short rows on the left with the given value, so they line up on the right.
+    AlignRight(T),
+}
+
+pub fn ragged_to_arr<T: Clone>(table: Vec<Vec<T>>, ragged: Ragged<T>) -> Result<Array2<T>> {
+    let rows = table.len();
+    let first = table.first().ok_or(anyhow!("no rows"))?.len();
+
+    let cols = match ragged {
+        Ragged::Reject => {
+            if let Some((i, row)) = table.iter().enumerate().find(|(_, row)| row.len() != first) {
+                bail!(
+                    "row {} has {} columns, but row 1 has {first}",
+                    i + 1,
+                    row.len()
+                );
+            }
+            first
+        }
+        Ragged::AlignLeft(_) | Ragged::AlignRight(_) => {
+            table.iter().map(Vec::len).max().unwrap_or(first)
+        }
+    };
+
+    let numbers: Vec<T> = table
+        .into_iter()
+        .flat_map(|row| {
+            let padding = cols - row.len();
+            match &ragged {
+                Ragged::Reject => row,
+                Ragged::AlignLeft(fill) => {
+                    let fill = std::iter::repeat_n(fill.clone(), padding);
+                    row.into_iter().chain(fill).collect()
+                }
+                Ragged::AlignRight(fill) => {
+                    let fill = std::iter::repeat_n(fill.clone(), padding);
+                    fill.chain(row).collect()
+                }
+            }
+        })
+        .collect();
+    let numbers = Array2::from_shape_vec((rows, cols), numbers)?;
+
+    Ok(numbers)
+}
+
+/// Lays out rows in left-aligned columns under a header.
+pub fn format_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let header = header.map(String::from);
+    let widths: [usize; N] = std::array::from_fn(|col| {
+        std::iter::once(&header)
+            .chain(rows.iter())
+            .map(|row| row[col].chars().count())
+            .max()
+            .unwrap_or(0)
+    });
+
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_rows() {
+        let table = || vec![vec![1, 2, 3], vec![4], vec![5, 6]];
+
+        let error = ragged_to_arr(table(), Ragged::Reject).unwrap_err();
+        assert_eq!(error.to_string(), "row 2 has 1 columns, but row 1 has 3");
+
+        let left = ragged_to_arr(table(), Ragged::AlignLeft(0)).unwrap();
+        assert_eq!(left, ndarray::array![[1, 2, 3], [4, 0, 0], [5, 6, 0]]);
+
+        let right = ragged_to_arr(table(), Ragged::AlignRight(0)).unwrap();
+        assert_eq!(right, ndarray::array![[1, 2, 3], [0, 0, 4], [0, 5, 6]]);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{cmp::minmax, ops::Range};
+
+use fxhash::FxHashMap;
+use itertools::Itertools;
+
+/// Compresses a set of coordinates onto consecutive indices. Each number gets an index of its own,
+/// and each gap between them is squashed into a single index, so shapes keep their topology.
+#[derive(Debug, Clone)]
+pub struct OrdinalMap {
+    ranges: Vec<Range<i64>>,
+    coord_cache: FxHashMap<i64, usize>,
+}
+
+impl OrdinalMap {
+    pub fn from_numbers(numbers: impl Iterator<Item = i64>) -> Self {
+        let ranges = numbers
+            .sorted()
+            .dedup()
+            .flat_map(|x| [x, x + 1])
+            .chain(std::iter::once(i64::MAX))
+            .scan(0, |prev, num| {
+                let ret = (*prev)..num;
+                *prev = num;
+                Some(ret)
+            })
+            .collect::<Vec<_>>();
+
+        let coord_cache = ranges
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.start, i))
+            .collect::<FxHashMap<_, _>>();
+
+        Self {
+            ranges,
+            coord_cache,
+        }
+    }
+
+    /// The number of indices, including the gaps.
+    pub fn num_ordinals(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The index of one of the numbers the map was built from.
+    pub fn ordinal(&self, number: i64) -> usize {
+        self.coord_cache[&number]
+    }
+
+    /// The length of the original number line covered by the indices from `a` to `b`, inclusive.
+    pub fn length_of_range_inclusive(&self, a: usize, b: usize) -> i64 {
+        let [min_idx, max_idx] = minmax(a, b);
+        self.ranges[max_idx].end - self.ranges[min_idx].start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordinal_map_from_numbers() {
+        let map = OrdinalMap::from_numbers([5, 2, 5].into_iter());
+
+        assert_eq!(map.ranges, vec![0..2, 2..3, 3..5, 5..6, 6..i64::MAX]);
+        assert_eq!(map.coord_cache[&2], 1);
+        assert_eq!(map.coord_cache[&5], 3);
+        assert_eq!(map.length_of_range_inclusive(3, 1), 4);
+    }
+}
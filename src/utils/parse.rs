@@ -0,0 +1,128 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Snippets longer than this are cut short in error messages.
+const MAX_SNIPPET_CHARS: usize = 40;
+
+/// A malformed piece of puzzle input: where it is, what it was, and what should have been there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based line the snippet starts on.
+    pub line: usize,
+    /// The 1-based column the snippet starts at, in characters.
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Builds an error for `snippet`, which should be a slice of `input`, working out its line and
+    /// column from where it sits. A snippet from anywhere else is reported at the end of `input`.
+    pub fn at(input: &str, snippet: &str, expected: impl Into<String>) -> Self {
+        let start = input
+            .substr_range(snippet)
+            .map_or(input.len(), |range| range.start);
+        let before = &input[..start];
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().unwrap_or("").chars().count() + 1,
+            snippet: snippet.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error for input that stopped before something it needed.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+
+        if self.snippet.is_empty() {
+            write!(f, "nothing")
+        } else if self.snippet.chars().count() > MAX_SNIPPET_CHARS {
+            let snippet = self
+                .snippet
+                .chars()
+                .take(MAX_SNIPPET_CHARS)
+                .collect::<String>();
+            write!(f, "`{snippet}...`")
+        } else {
+            write!(f, "`{}`", self.snippet)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `field`, a slice of `input`, reporting where it is if it's malformed.
+pub fn parse_at<T: FromStr>(input: &str, field: &str, expected: &str) -> Result<T, ParseError> {
+    field
+        .parse()
+        .map_err(|_| ParseError::at(input, field, expected))
+}
+
+/// Splits a string into one slice per character, so each one can be reported with
+/// [`ParseError::at`].
+pub fn char_slices(s: &str) -> impl Iterator<Item = &str> {
+    s.char_indices().map(|(i, ch)| &s[i..i + ch.len_utf8()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_position() {
+        let input = "L68\nR1x\n";
+        let error = ParseError::at(input, &input[6..7], "a digit");
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a digit, found `x`"
+        );
+
+        let error = ParseError::at_end(input, "another rotation");
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected another rotation, found nothing"
+        );
+    }
+
+    #[test]
+    fn long_snippets_are_cut_short() {
+        let input = "x".repeat(50);
+        let error = ParseError::at(&input, &input, "something shorter");
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "line 1, column 1: expected something shorter, found `{}...`",
+                "x".repeat(40)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_fields() {
+        let input = "3-x";
+
+        assert_eq!(parse_at::<u64>(input, &input[..1], "a number"), Ok(3));
+        assert_eq!(
+            parse_at::<u64>(input, &input[2..], "a number")
+                .unwrap_err()
+                .column,
+            3
+        );
+        assert_eq!(char_slices("a€b").collect::<Vec<_>>(), ["a", "€", "b"]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::ops::RangeInclusive;
+
+use fxhash::FxHashSet;
+use itertools::Itertools;
+
+/// A set of inclusive ranges of integers.
+#[derive(Debug, Default, Clone)]
+pub struct RangeSet(FxHashSet<RangeInclusive<u64>>);
+
+impl FromIterator<RangeInclusive<u64>> for RangeSet {
+    fn from_iter<T: IntoIterator<Item = RangeInclusive<u64>>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl RangeSet {
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<u64>> {
+        self.0.iter()
+    }
+
+    pub fn contains(&self, item: &u64) -> bool {
+        self.0.iter().any(|range| range.contains(item))
+    }
+
+    /// Merges two ranges into one if they overlap.
+    pub fn try_combine_ranges(
+        first: &RangeInclusive<u64>,
+        second: &RangeInclusive<u64>,
+    ) -> Option<RangeInclusive<u64>> {
+        let (first, second) = {
+            if first.start() < second.start() {
+                (first, second)
+            } else {
+                (second, first)
+            }
+        };
+
+        if first.end() >= second.start() {
+            let start = u64::min(*first.start(), *second.start());
+            let end = u64::max(*first.end(), *second.end());
+            Some(start..=end)
+        } else {
+            None
+        }
+    }
+
+    /// Merges overlapping ranges until none of them overlap.
+    pub fn coalesce_ranges(&mut self) {
+        let mut sets_to_remove: FxHashSet<RangeInclusive<u64>> = Default::default();
+        let mut sets_to_add: FxHashSet<RangeInclusive<u64>> = Default::default();
+
+        loop {
+            self.0
+                .iter()
+                .cloned()
+                .array_combinations()
+                .for_each(|[first, second]| {
+                    // Don't try to coalesce ranges that are already marked for removal.
+                    if sets_to_remove.contains(&first) || sets_to_remove.contains(&second) {
+                        return;
+                    }
+
+                    if let Some(new_range) = Self::try_combine_ranges(&first, &second) {
+                        sets_to_add.insert(new_range);
+                        sets_to_remove.insert(first);
+                        sets_to_remove.insert(second);
+                    }
+                });
+
+            if sets_to_add.is_empty() {
+                break;
+            }
+
+            self.0 = &(&self.0 - &sets_to_remove) | &sets_to_add;
+            sets_to_remove.clear();
+            sets_to_add.clear();
+        }
+    }
+
+    /// The number of integers in the set, assuming its ranges have been coalesced.
+    pub fn size_of_ranges(&self) -> u64 {
+        self.0
+            .iter()
+            .map(|range| (range.end() - range.start()) + 1)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_combine_ranges() {
+        assert_eq!(
+            RangeSet::try_combine_ranges(&(1..=5), &(3..=8)),
+            Some(1..=8)
+        );
+        assert_eq!(
+            RangeSet::try_combine_ranges(&(3..=8), &(1..=5)),
+            Some(1..=8)
+        );
+        assert_eq!(
+            RangeSet::try_combine_ranges(&(1..=10), &(2..=3)),
+            Some(1..=10)
+        );
+        assert_eq!(
+            RangeSet::try_combine_ranges(&(1..=5), &(5..=6)),
+            Some(1..=6)
+        );
+        assert_eq!(RangeSet::try_combine_ranges(&(1..=2), &(4..=5)), None);
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use log::warn;
+
+use crate::{
+    answers::{ExpectedAnswers, default_answers_path, sibling_answers_path},
+    challenge::{Answer, ChallengeInfo, Part},
+    runner::{self, PartReport, Report, default_input_path},
+};
+
+/// The modification time of every watched file, or `None` for ones that don't exist.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .flat_map(|path| match std::fs::read_dir(path) {
+            Ok(entries) => {
+                let mut files = entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .collect::<Vec<_>>();
+                files.sort();
+                files
+            }
+            Err(_) => vec![path.clone()],
+        })
+        .map(|path| {
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Polls files, and the files directly inside directories, for changes to their modification
+/// times. Files being created or removed count as changes too.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let snapshot = snapshot(&paths);
+        Self { paths, snapshot }
+    }
+
+    /// Whether anything has changed since the watcher was created or last polled.
+    pub fn poll(&mut self) -> bool {
+        let snapshot = snapshot(&self.paths);
+        let changed = snapshot != self.snapshot;
+        self.snapshot = snapshot;
+        changed
+    }
+}
+
+/// Describes a part's answer, and how it compares to the answer from the previous run, or the
+/// error it returned instead.
+pub fn describe_part(part: &PartReport, previous: Option<&Answer>) -> String {
+    let answer = match &part.answer {
+        Ok(answer) => answer,
+        Err(error) => {
+            return format!(
+                "Part {}: error: {error:#} in {:.2?}",
+                part.part, part.elapsed
+            );
+        }
+    };
+    let change = match previous {
+        None => String::new(),
+        Some(previous) if previous == answer => ", unchanged".to_string(),
+        Some(previous) => format!(", was {previous}"),
+    };
+
+    format!(
+        "Part {}: {answer} ({}{change}) in {:.2?}",
+        part.part, part.verdict, part.elapsed
+    )
+}
+
+/// Re-runs a challenge against its input, and optionally a directory of fixtures, whenever they
+/// change.
+pub struct Session<'a> {
+    info: &'a ChallengeInfo,
+    input: PathBuf,
+    fixtures: Option<PathBuf>,
+    timeout: Option<Duration>,
+    previous: HashMap<(PathBuf, Part), Answer>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(
+        info: &'a ChallengeInfo,
+        input: PathBuf,
+        fixtures: Option<PathBuf>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            info,
+            input,
+            fixtures,
+            timeout,
+            previous: HashMap::new(),
+        }
+    }
+
+    /// The input and answers files to run, in order.
+    fn runs(&self) -> Vec<(PathBuf, Option<PathBuf>)> {
+        // Like `aoc run`, only the default input is checked against the default answers.
+        let answers = (self.input == default_input_path(self.info.day))
+            .then(|| default_answers_path(self.info.day));
+        let mut runs = vec![(self.input.clone(), answers)];
+
+        if let Some(dir) = &self.fixtures {
+            match runner::inputs_in(dir) {
+                Ok(inputs) => runs.extend(inputs.into_iter().map(|input| {
+                    let answers = sibling_answers_path(&input);
+                    (input, Some(answers))
+                })),
+                Err(error) => warn!("{error:#}"),
+            }
+        }
+
+        runs
+    }
+
+    fn run_one(&self, input: &Path, answers: Option<&Path>) -> Result<Report> {
+        let expected = match answers {
+            Some(path) => ExpectedAnswers::load(path)?,
+            None => ExpectedAnswers::default(),
+        };
+        let contents = std::fs::read_to_string(input)?;
+
+        Ok(runner::run(
+            self.info,
+            &contents,
+            &[Part::One, Part::Two],
+            &expected,
+            self.timeout,
+        ))
+    }
+
+    /// Runs everything once, printing the answers and how they've changed since the last run.
+    pub fn run(&mut self) {
+        for (input, answers) in self.runs() {
+            println!("{}:", input.display());
+
+            let report = match self.run_one(&input, answers.as_deref()) {
+                Ok(report) => report,
+                Err(error) => {
+                    println!("  error: {error:#}");
+                    continue;
+                }
+            };
+
+            for part in &report.parts {
+                let key = (input.clone(), part.part);
+                println!("  {}", describe_part(part, self.previous.get(&key)));
+                // Keep the last answer through an error, so a fix shows whether it changed.
+                if let Ok(answer) = &part.answer {
+                    self.previous.insert(key, answer.clone());
+                }
+            }
+            if report.is_error() {
+                println!("  {}", report.status);
+            }
+        }
+    }
+
+    /// Runs everything, then again every time one of the inputs changes. Never returns.
+    pub fn watch(&mut self, interval: Duration) -> ! {
+        let mut paths = vec![self.input.clone()];
+        paths.extend(self.fixtures.clone());
+        let mut watcher = Watcher::new(paths);
+
+        loop {
+            self.run();
+            println!("Watching for changes...");
+
+            while !watcher.poll() {
+                thread::sleep(interval);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+    use crate::answers::Verdict;
+
+    #[test]
+    fn watcher_notices_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.txt");
+        std::fs::write(&input, "1").unwrap();
+
+        let mut watcher = Watcher::new(vec![input.clone(), dir.path().join("fixtures")]);
+        assert!(!watcher.poll());
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        std::fs::create_dir(dir.path().join("fixtures")).unwrap();
+        std::fs::write(dir.path().join("fixtures/example.txt"), "2").unwrap();
+        assert!(watcher.poll());
+
+        std::fs::remove_file(&input).unwrap();
+        assert!(watcher.poll());
+    }
+
+    #[test]
+    fn parts_are_compared_with_the_previous_run() {
+        let part = PartReport {
+            part: Part::One,
+            answer: Ok(42.into()),
+            verdict: Verdict::Unknown,
+            elapsed: Duration::from_millis(5),
+        };
+
+        assert_eq!(describe_part(&part, None), "Part 1: 42 (unknown) in 5.00ms");
+        assert_eq!(
+            describe_part(&part, Some(&42.into())),
+            "Part 1: 42 (unknown, unchanged) in 5.00ms"
+        );
+        assert_eq!(
+            describe_part(&part, Some(&41.into())),
+            "Part 1: 42 (unknown, was 41) in 5.00ms"
+        );
+
+        let failed = PartReport {
+            answer: Err(anyhow::anyhow!("No device named you")),
+            ..part
+        };
+        assert_eq!(
+            describe_part(&failed, Some(&42.into())),
+            "Part 1: error: No device named you in 5.00ms"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use aoc::{
+    challenge::{self, AOCChallenge, Answer, Part, challenge_5},
+    utils::RangeSet,
+};
+
+#[test]
+fn run_a_registered_challenge() {
+    let input = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/fixtures/challenge_1/example.txt"
+    ))
+    .unwrap();
+
+    let result = challenge::get(1).unwrap().run(&input).unwrap();
+
+    assert_eq!(result.part(Part::One), &Answer::from(3));
+    assert_eq!(result.part(Part::Two), &Answer::from(6));
+}
+
+#[test]
+fn use_a_solver_directly() {
+    let challenge = challenge_5::Challenge;
+    let inventory = challenge.parse("3-5\n10-14\n\n1\n5\n12\n").unwrap();
+
+    assert_eq!(challenge.part_1(&inventory).unwrap(), Answer::from(2));
+    assert_eq!(challenge.part_2(&inventory).unwrap(), Answer::from(8));
+}
+
+#[test]
+fn range_sets() {
+    let mut ranges = [1..=3, 2..=6, 10..=10].into_iter().collect::<RangeSet>();
+    ranges.coalesce_ranges();
+
+    assert_eq!(ranges.size_of_ranges(), 7);
+    assert!(ranges.contains(&10));
+    assert!(!ranges.contains(&8));
+}